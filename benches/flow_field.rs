@@ -0,0 +1,163 @@
+//! Compares `FlowField::compute` on the dense tile grid with the old `Vec::contains` lookups.
+//!
+//! Run with `cargo bench --bench flow_field`.
+
+use std::collections::VecDeque;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use bevy::prelude::UVec2;
+use towerdefense::core::map::Map;
+use towerdefense::entities::pathfinding::{FlowDirection, FlowField};
+
+const ITERATIONS: u32 = 20;
+
+/// Serpentine path filling the map: right along one row, down two, back left, ...
+fn serpentine_map(width: u32, height: u32) -> Map {
+    let mut path_tiles = Vec::new();
+    let mut y = 0;
+    let mut left_to_right = true;
+
+    while y < height {
+        let row: Vec<u32> = if left_to_right {
+            (0..width).collect()
+        } else {
+            (0..width).rev().collect()
+        };
+        for x in row {
+            path_tiles.push(UVec2::new(x, y));
+        }
+
+        // Connect to the next row at the end we finished on
+        let edge_x = if left_to_right { width - 1 } else { 0 };
+        if y + 1 < height {
+            path_tiles.push(UVec2::new(edge_x, y + 1));
+        }
+
+        y += 2;
+        left_to_right = !left_to_right;
+    }
+
+    let end = *path_tiles.last().unwrap();
+    let buildable_tiles = (0..height)
+        .filter(|y| y % 2 == 1)
+        .flat_map(|y| (0..width).map(move |x| UVec2::new(x, y)))
+        .filter(|pos| !path_tiles.contains(pos))
+        .collect();
+
+    Map::from_tiles(UVec2::new(width, height), path_tiles, buildable_tiles, vec![UVec2::ZERO], vec![end])
+}
+
+/// `FlowField::compute` as it was before `Map` had a dense tile grid
+fn legacy_compute(field: &mut FlowField, map: &Map, goal_pos: UVec2) {
+    field.integration_field = vec![u32::MAX; field.width * field.height];
+    field.field = vec![None; field.width * field.height];
+
+    let mut queue = VecDeque::new();
+    let goal_index = field.get_index(goal_pos.x as usize, goal_pos.y as usize);
+    field.integration_field[goal_index] = 0;
+    queue.push_back(goal_index);
+
+    while let Some(current_index) = queue.pop_front() {
+        let current_cost = field.integration_field[current_index];
+        let (x, y) = field.get_coordinates(current_index);
+
+        for (nx, ny) in [(x + 1, y), (x.wrapping_sub(1), y), (x, y + 1), (x, y.wrapping_sub(1))] {
+            if nx >= field.width || ny >= field.height {
+                continue;
+            }
+            if !map.path_tiles.contains(&UVec2::new(nx as u32, ny as u32)) {
+                continue;
+            }
+            let neighbor_index = field.get_index(nx, ny);
+            if current_cost + 10 < field.integration_field[neighbor_index] {
+                field.integration_field[neighbor_index] = current_cost + 10;
+                queue.push_back(neighbor_index);
+            }
+        }
+    }
+
+    for &path_pos in &map.path_tiles {
+        let (x, y) = (path_pos.x as usize, path_pos.y as usize);
+        let index = field.get_index(x, y);
+        if field.integration_field[index] == u32::MAX {
+            continue;
+        }
+
+        let mut best_direction = FlowDirection::None;
+        let mut best_cost = field.integration_field[index];
+
+        for (nx, ny, direction) in [
+            (x + 1, y, FlowDirection::East),
+            (x.wrapping_sub(1), y, FlowDirection::West),
+            (x, y + 1, FlowDirection::South),
+            (x, y.wrapping_sub(1), FlowDirection::North),
+        ] {
+            if nx >= field.width || ny >= field.height {
+                continue;
+            }
+            let neighbor_index = field.get_index(nx, ny);
+            if !map.path_tiles.contains(&UVec2::new(nx as u32, ny as u32))
+                || field.integration_field[neighbor_index] == u32::MAX
+            {
+                continue;
+            }
+            if field.integration_field[neighbor_index] < best_cost {
+                best_cost = field.integration_field[neighbor_index];
+                best_direction = direction;
+            }
+        }
+
+        field.field[index] = Some(best_direction);
+    }
+
+    field.is_initialized = true;
+}
+
+fn time_it(mut run: impl FnMut()) -> Duration {
+    // Warm up once so allocation of the first run isn't counted
+    run();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        run();
+    }
+    start.elapsed() / ITERATIONS
+}
+
+fn main() {
+    println!("{:>10} {:>8} {:>14} {:>14} {:>8}", "map", "path", "legacy", "dense", "speedup");
+
+    for (width, height) in [(27, 15), (64, 64), (128, 128), (256, 256)] {
+        let map = serpentine_map(width, height);
+        let goal = map.ends[0];
+
+        // Only worth timing if both give the same field
+        let mut legacy_field = FlowField::new(width as usize, height as usize);
+        legacy_compute(&mut legacy_field, &map, goal);
+        let mut dense_field = FlowField::new(width as usize, height as usize);
+        dense_field.compute(&map, &[goal]);
+        assert_eq!(legacy_field.integration_field, dense_field.integration_field);
+        assert_eq!(legacy_field.field, dense_field.field);
+
+        let legacy = time_it(|| {
+            let mut field = FlowField::new(width as usize, height as usize);
+            legacy_compute(&mut field, &map, goal);
+            black_box(&field);
+        });
+
+        let dense = time_it(|| {
+            let mut field = FlowField::new(width as usize, height as usize);
+            field.compute(&map, &[goal]);
+            black_box(&field);
+        });
+
+        println!(
+            "{:>10} {:>8} {:>14?} {:>14?} {:>7.1}x",
+            format!("{}x{}", width, height),
+            map.path_tiles.len(),
+            legacy,
+            dense,
+            legacy.as_secs_f64() / dense.as_secs_f64().max(f64::EPSILON),
+        );
+    }
+}
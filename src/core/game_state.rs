@@ -1,4 +1,7 @@
 use bevy::prelude::*;
+use crate::levels::level::LevelCompleteEvent;
+
+pub const STARTING_HEALTH: u32 = 100;
 
 pub struct GameStatePlugin;
 
@@ -6,9 +9,10 @@ impl Plugin for GameStatePlugin {
     fn build(&self, app: &mut App) {
         app.init_state::<GameState>()
            .init_resource::<PlayerResource>()
+           .init_resource::<GameMode>()
            .add_systems(Startup, setup_game)
-           .add_systems(OnEnter(GameState::Playing), reset_player_resources)
            .add_systems(Update, check_game_over_condition.run_if(in_state(GameState::Playing)))
+           .add_systems(Update, check_victory_condition.run_if(in_state(GameState::Playing)))
            .add_systems(Update, handle_pause.run_if(in_state(GameState::Playing)))
            .add_systems(Update, handle_resume.run_if(in_state(GameState::Paused)));
     }
@@ -21,9 +25,18 @@ pub enum GameState {
     Playing,
     Paused,
     GameOver,
+    Victory,
     Editor,
 }
 
+/// How the next run is played, picked from the main menu
+#[derive(Resource, Debug, Clone, Copy, Eq, PartialEq, Default)]
+pub enum GameMode {
+    #[default]
+    Campaign,
+    Endless, // Waves keep coming after the level's own run out
+}
+
 #[derive(Resource, Default)]
 pub struct PlayerResource {
     pub money: u32,
@@ -31,10 +44,33 @@ pub struct PlayerResource {
     pub score: u32,
 }
 
+impl PlayerResource {
+    /// One to three stars depending on how much health is left
+    pub fn star_rating(&self) -> u32 {
+        let health_left = self.health as f32 / STARTING_HEALTH as f32;
+        if health_left >= 0.9 {
+            3
+        } else if health_left >= 0.5 {
+            2
+        } else {
+            1
+        }
+    }
+
+    /// Back to the starting money and health, for a fresh level
+    pub fn reset(&mut self) {
+        *self = PlayerResource {
+            money: 100,
+            health: STARTING_HEALTH,
+            score: 0,
+        };
+    }
+}
+
 fn setup_game(mut commands: Commands) {
     commands.insert_resource(PlayerResource {
         money: 100,
-        health: 100,
+        health: STARTING_HEALTH,
         score: 0,
     });
 }
@@ -49,13 +85,17 @@ fn check_game_over_condition(
     }
 }
 
-// Reset player resources when starting a new game
-pub fn reset_player_resources(mut player_resource: ResMut<PlayerResource>) {
-    *player_resource = PlayerResource {
-        money: 100,
-        health: 100,
-        score: 0,
-    };
+fn check_victory_condition(
+    mut level_complete_events: EventReader<LevelCompleteEvent>,
+    player_resource: Res<PlayerResource>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for event in level_complete_events.read() {
+        if player_resource.health > 0 {
+            info!("Level {} won", event.level);
+            next_state.set(GameState::Victory);
+        }
+    }
 }
 
 fn handle_pause(
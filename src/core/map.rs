@@ -1,4 +1,6 @@
-use bevy::{prelude::{Component, Resource, UVec2, Vec2, Vec3}, sprite::TextureAtlas};
+use std::collections::HashMap;
+
+use bevy::{prelude::{Component, Entity, Resource, UVec2, Vec2, Vec3}, sprite::TextureAtlas};
 
 use crate::core::config::{CELL_SIZE, GRID_HEIGHT, GRID_WIDTH, WINDOW_HEIGHT, WINDOW_WIDTH};
 
@@ -8,26 +10,37 @@ pub struct GridCell {
     pub y: usize,
 }
 
+/// What a single grid cell is used for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TileKind {
+    #[default]
+    Empty,
+    Path,
+    Buildable,
+}
+
+/// Build with `Map::from_tiles` so the dense grid matches the tile lists
 #[derive(Resource)]
 pub struct Map {
     pub grid_size: Vec2,             
     pub dimensions: UVec2,           
     pub path_tiles: Vec<UVec2>,      
     pub buildable_tiles: Vec<UVec2>, 
-    pub start: UVec2,               
-    pub end: UVec2,                 
+    pub starts: Vec<UVec2>,          // Spawn portals, the first one is the default
+    pub ends: Vec<UVec2>,            // Exits, enemies leak at whichever is closest
+    pub occupancy: HashMap<UVec2, Entity>, // Tower standing on each occupied tile
+    tiles: Vec<TileKind>,                  // Row-major, dimensions.x * dimensions.y
 }
 
 impl Default for Map {
     fn default() -> Self {
-        Map {
-            grid_size: Vec2::new(CELL_SIZE, CELL_SIZE),
-            dimensions: UVec2::new(GRID_WIDTH as u32, GRID_HEIGHT as u32),
-            path_tiles: Vec::new(),      // Will be filled based on level data
-            buildable_tiles: Vec::new(), // Will be filled later
-            start: UVec2::new(0, GRID_HEIGHT as u32 / 2),
-            end: UVec2::new(GRID_WIDTH as u32 - 1, GRID_HEIGHT as u32 / 2),
-        }
+        Map::from_tiles(
+            UVec2::new(GRID_WIDTH as u32, GRID_HEIGHT as u32),
+            Vec::new(), // Will be filled based on level data
+            Vec::new(), // Will be filled later
+            vec![UVec2::new(0, GRID_HEIGHT as u32 / 2)],
+            vec![UVec2::new(GRID_WIDTH as u32 - 1, GRID_HEIGHT as u32 / 2)],
+        )
     }
 }
 
@@ -35,6 +48,75 @@ impl Map {
     pub fn new() -> Self {
         Self::default()
     }
+
+    pub fn from_tiles(
+        dimensions: UVec2,
+        path_tiles: Vec<UVec2>,
+        buildable_tiles: Vec<UVec2>,
+        starts: Vec<UVec2>,
+        ends: Vec<UVec2>,
+    ) -> Self {
+        let mut map = Map {
+            grid_size: Vec2::new(CELL_SIZE, CELL_SIZE),
+            dimensions,
+            path_tiles,
+            buildable_tiles,
+            starts,
+            ends,
+            occupancy: HashMap::new(),
+            tiles: vec![TileKind::Empty; (dimensions.x * dimensions.y) as usize],
+        };
+
+        // Tiles outside the map are kept in the lists but can't be looked up
+        for &pos in &map.buildable_tiles {
+            if let Some(index) = map.tile_index(pos) {
+                map.tiles[index] = TileKind::Buildable;
+            }
+        }
+        // Path wins if a level lists a tile as both
+        for &pos in &map.path_tiles {
+            if let Some(index) = map.tile_index(pos) {
+                map.tiles[index] = TileKind::Path;
+            }
+        }
+
+        map
+    }
+
+    fn tile_index(&self, grid_pos: UVec2) -> Option<usize> {
+        if grid_pos.x >= self.dimensions.x || grid_pos.y >= self.dimensions.y {
+            return None;
+        }
+        Some((grid_pos.y * self.dimensions.x + grid_pos.x) as usize)
+    }
+
+    pub fn tile_kind(&self, grid_pos: UVec2) -> TileKind {
+        self.tile_index(grid_pos)
+            .map(|index| self.tiles[index])
+            .unwrap_or_default()
+    }
+
+    /// Spawn portal by index, falling back to the first one if the level has no such portal
+    pub fn spawn_point(&self, index: usize) -> UVec2 {
+        self.starts
+            .get(index)
+            .or(self.starts.first())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// World position of the exit closest to `world_pos`
+    pub fn nearest_end(&self, world_pos: Vec2) -> Vec2 {
+        self.ends
+            .iter()
+            .map(|&end| self.grid_to_world(end))
+            .min_by(|a, b| a.distance_squared(world_pos).total_cmp(&b.distance_squared(world_pos)))
+            .unwrap_or(world_pos)
+    }
+
+    pub fn is_path(&self, grid_pos: UVec2) -> bool {
+        self.tile_kind(grid_pos) == TileKind::Path
+    }
     pub fn grid_to_world(&self, grid_pos: UVec2) -> Vec2 {
         let grid_origin_x = -WINDOW_WIDTH / 2.0;
         let grid_origin_y = WINDOW_HEIGHT / 2.0;
@@ -70,7 +152,38 @@ impl Map {
     }
 
     pub fn is_buildable(&self, grid_pos: UVec2) -> bool {
-        self.buildable_tiles.contains(&grid_pos)
+        self.tile_kind(grid_pos) == TileKind::Buildable
+    }
+
+    /// Buildable and not already taken by a tower
+    pub fn can_build(&self, grid_pos: UVec2) -> bool {
+        self.is_buildable(grid_pos) && !self.is_occupied(grid_pos)
+    }
+
+    pub fn is_occupied(&self, grid_pos: UVec2) -> bool {
+        self.occupancy.contains_key(&grid_pos)
+    }
+
+    pub fn tower_at(&self, grid_pos: UVec2) -> Option<Entity> {
+        self.occupancy.get(&grid_pos).copied()
+    }
+
+    /// Marks a tile as taken by a tower
+    pub fn occupy(&mut self, grid_pos: UVec2, tower: Entity) {
+        self.occupancy.insert(grid_pos, tower);
+    }
+
+    /// Frees a tile, returning the tower that was on it
+    pub fn vacate(&mut self, grid_pos: UVec2) -> Option<Entity> {
+        self.occupancy.remove(&grid_pos)
+    }
+
+    /// Tile a tower is standing on
+    pub fn tile_of(&self, tower: Entity) -> Option<UVec2> {
+        self.occupancy
+            .iter()
+            .find(|(_, entity)| **entity == tower)
+            .map(|(&pos, _)| pos)
     }
 
     pub fn get_adjacent_tiles(&self, pos: UVec2) -> Vec<UVec2> {
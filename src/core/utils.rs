@@ -9,6 +9,13 @@ pub fn is_point_in_radius(center: Vec2, point: Vec2, radius: f32) -> bool {
     distance(center, point) <= radius
 }
 
+/// True when the cursor is over an interactive UI node, so world clicks should be ignored
+pub fn is_pointer_over_ui<'a>(interactions: impl IntoIterator<Item = &'a Interaction>) -> bool {
+    interactions
+        .into_iter()
+        .any(|interaction| *interaction != Interaction::None)
+}
+
 pub fn find_path(start: Vec2, end: Vec2, obstacles: &[Vec2]) -> Vec<Vec2> {
     // Simple pathfinding implementation
     // In a real game, you'd use A* or another algorithm
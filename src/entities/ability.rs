@@ -0,0 +1,180 @@
+use crate::core::game_state::GameState;
+use crate::core::map::Map;
+use crate::core::utils::is_point_in_radius;
+use crate::entities::enemy::{spawn_enemy_at, Enemy, EnemyCatalog, EnemyType, MovementMode, SpawnModifiers};
+use bevy::math::Vec3Swizzles;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+pub struct AbilityPlugin;
+
+impl Plugin for AbilityPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, tick_healing_abilities.run_if(in_state(GameState::Playing)));
+    }
+}
+
+// How far apart enemies spawned on death are placed
+const DEATH_SPAWN_SPREAD: f32 = 10.0;
+// Size of each piece relative to the enemy that split
+const SPLIT_SCALE: f32 = 0.7;
+
+/// Behaviour attached to an enemy archetype in assets/enemies.json
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind")]
+pub enum EnemyAbility {
+    SpawnOnDeath { enemy_type: EnemyType, count: u32 }, // Releases other enemies when killed
+    Split { count: u32 },                               // Breaks into smaller copies of itself
+    Heal { radius: f32, per_second: f32 },              // Regenerates nearby allies
+    Shield { amount: f32 },                             // Soaks damage before health
+}
+
+/// Abilities of a single enemy
+#[derive(Component, Debug, Clone, Default)]
+pub struct Abilities(pub Vec<EnemyAbility>);
+
+/// Total shield an enemy starts with
+pub fn shield_amount(abilities: &[EnemyAbility]) -> f32 {
+    abilities
+        .iter()
+        .map(|ability| match ability {
+            EnemyAbility::Shield { amount } => *amount,
+            _ => 0.0,
+        })
+        .sum()
+}
+
+/// Runs the on-death abilities of an enemy that was just killed, returning the enemies it spawned
+pub fn trigger_death_abilities(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    map: &Map,
+    catalog: &EnemyCatalog,
+    enemy: &Enemy,
+    abilities: &Abilities,
+    position: Vec2,
+) -> Vec<Entity> {
+    let mut spawned = Vec::new();
+    for ability in abilities.0.iter() {
+        match ability {
+            EnemyAbility::SpawnOnDeath { enemy_type, count } => {
+                for i in 0..*count {
+                    spawned.extend(spawn_enemy_at(
+                        commands,
+                        asset_server,
+                        catalog,
+                        enemy_type.clone(),
+                        death_spawn_position(map, catalog, enemy_type, position, i, *count),
+                        SpawnModifiers::default(),
+                    ));
+                }
+            }
+            EnemyAbility::Split { count } => {
+                let count = (*count).max(1);
+                let modifiers = SpawnModifiers {
+                    health: enemy.max_health / count as f32 / archetype_health(catalog, &enemy.enemy_type),
+                    scale: SPLIT_SCALE,
+                    reward: 1.0 / count as f32,
+                };
+                // Pieces keep every other ability but don't split again
+                let remaining: Vec<EnemyAbility> = abilities
+                    .0
+                    .iter()
+                    .filter(|a| !matches!(a, EnemyAbility::Split { .. }))
+                    .cloned()
+                    .collect();
+
+                for i in 0..count {
+                    if let Some(piece) = spawn_enemy_at(
+                        commands,
+                        asset_server,
+                        catalog,
+                        enemy.enemy_type.clone(),
+                        death_spawn_position(map, catalog, &enemy.enemy_type, position, i, count),
+                        modifiers,
+                    ) {
+                        commands.entity(piece).insert(Abilities(remaining.clone()));
+                        spawned.push(piece);
+                    }
+                }
+            }
+            EnemyAbility::Heal { .. } | EnemyAbility::Shield { .. } => {}
+        }
+    }
+    spawned
+}
+
+fn archetype_health(catalog: &EnemyCatalog, enemy_type: &EnemyType) -> f32 {
+    catalog
+        .get(enemy_type)
+        .map(|definition| definition.health)
+        .filter(|health| *health > 0.0)
+        .unwrap_or(1.0)
+}
+
+// Places spawned enemies on a small circle so they don't stack up exactly
+fn spread_offset(index: u32, count: u32) -> Vec2 {
+    if count <= 1 {
+        return Vec2::ZERO;
+    }
+    let angle = index as f32 / count as f32 * std::f32::consts::TAU;
+    Vec2::from_angle(angle) * DEATH_SPAWN_SPREAD
+}
+
+// Spreads spawned enemies out, unless that would put a ground enemy off the path
+fn death_spawn_position(
+    map: &Map,
+    catalog: &EnemyCatalog,
+    enemy_type: &EnemyType,
+    position: Vec2,
+    index: u32,
+    count: u32,
+) -> Vec2 {
+    let spread = position + spread_offset(index, count);
+    let flies = catalog
+        .get(enemy_type)
+        .is_some_and(|definition| definition.movement == MovementMode::Air);
+    if flies || map.is_path(map.world_to_grid(spread)) {
+        spread
+    } else {
+        position
+    }
+}
+
+fn tick_healing_abilities(time: Res<Time>, mut enemies: Query<(Entity, &Transform, &mut Enemy, &Abilities)>) {
+    let delta = time.delta_secs();
+
+    let healers: Vec<(Entity, Vec2, f32, f32)> = enemies
+        .iter()
+        .filter(|(_, _, enemy, _)| enemy.health > 0.0)
+        .flat_map(|(entity, transform, _, abilities)| {
+            abilities.0.iter().filter_map(move |ability| match ability {
+                EnemyAbility::Heal { radius, per_second } => {
+                    Some((entity, transform.translation.xy(), *radius, *per_second))
+                }
+                _ => None,
+            })
+        })
+        .collect();
+
+    if healers.is_empty() {
+        return;
+    }
+
+    for (entity, transform, mut enemy, _) in enemies.iter_mut() {
+        if enemy.health <= 0.0 || enemy.health >= enemy.max_health {
+            continue;
+        }
+        let position = transform.translation.xy();
+        let healing: f32 = healers
+            .iter()
+            .filter(|(healer, healer_pos, radius, _)| {
+                *healer != entity && is_point_in_radius(*healer_pos, position, *radius)
+            })
+            .map(|(_, _, _, per_second)| per_second * delta)
+            .sum();
+        if healing > 0.0 {
+            enemy.health = (enemy.health + healing).min(enemy.max_health);
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use crate::core::game_state::GameState;
+use crate::entities::enemy::Enemy;
+use crate::levels::level::LevelEntity;
+use bevy::prelude::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+pub struct AnimationPlugin;
+
+impl Plugin for AnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (play_enemy_hit_animations, animate_sprites, despawn_finished_death_animations)
+                .chain()
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// Grid layout of a sprite sheet
+#[derive(Clone, Debug, Deserialize)]
+pub struct SpriteAtlas {
+    pub tile_size: [u32; 2],
+    pub columns: u32,
+    pub rows: u32,
+    #[serde(default)]
+    pub index: usize, // Frame shown when the sprite is not animated
+}
+
+impl SpriteAtlas {
+    pub fn layout(&self) -> TextureAtlasLayout {
+        TextureAtlasLayout::from_grid(
+            UVec2::new(self.tile_size[0], self.tile_size[1]),
+            self.columns,
+            self.rows,
+            None,
+            None,
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+pub enum AnimationName {
+    Idle,
+    Walk,
+    Hit,
+    Die,
+    Attack,
+}
+
+impl AnimationName {
+    /// Looping clips play until something else is picked, the rest play once
+    fn loops(&self) -> bool {
+        matches!(self, AnimationName::Idle | AnimationName::Walk)
+    }
+}
+
+/// A run of frames in a sprite sheet
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct AnimationClip {
+    pub first: usize,
+    pub last: usize,
+    pub fps: f32,
+}
+
+/// Clips by name, as defined next to the enemy and tower data
+pub type AnimationClips = HashMap<AnimationName, AnimationClip>;
+
+/// Builds a sprite, cut from a sheet when the definition has one
+pub fn sheet_sprite(
+    image: Handle<Image>,
+    atlas: Option<&SpriteAtlas>,
+    layout: Option<Handle<TextureAtlasLayout>>,
+) -> Sprite {
+    Sprite {
+        image,
+        texture_atlas: atlas.zip(layout).map(|(atlas, layout)| TextureAtlas {
+            layout,
+            index: atlas.index,
+        }),
+        ..default()
+    }
+}
+
+#[derive(Component, Clone)]
+pub struct SpriteAnimation {
+    clips: AnimationClips,
+    default_clip: AnimationName, // Played again once a one-shot clip ends
+    current: AnimationName,
+    frame: usize,
+    timer: Timer,
+    finished: bool,
+}
+
+impl SpriteAnimation {
+    /// Starts on `default_clip`, returns None if there is nothing to animate
+    pub fn new(clips: &AnimationClips, default_clip: AnimationName) -> Option<Self> {
+        if clips.is_empty() {
+            return None;
+        }
+        let mut animation = SpriteAnimation {
+            clips: clips.clone(),
+            default_clip,
+            current: default_clip,
+            frame: 0,
+            timer: Timer::from_seconds(1.0, TimerMode::Repeating),
+            finished: false,
+        };
+        animation.restart(default_clip);
+        Some(animation)
+    }
+
+    /// Switches to another clip, a clip that is already playing carries on
+    pub fn play(&mut self, name: AnimationName) {
+        if (name != self.current || self.finished) && self.clips.contains_key(&name) {
+            self.restart(name);
+        }
+    }
+
+    pub fn has_clip(&self, name: AnimationName) -> bool {
+        self.clips.contains_key(&name)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    fn restart(&mut self, name: AnimationName) {
+        self.current = name;
+        self.frame = 0;
+        self.finished = false;
+        if let Some(clip) = self.clips.get(&name) {
+            self.timer = Timer::from_seconds(1.0 / clip.fps.max(0.01), TimerMode::Repeating);
+        }
+    }
+
+    fn atlas_index(&self) -> Option<usize> {
+        self.clips.get(&self.current).map(|clip| clip.first + self.frame)
+    }
+}
+
+/// Health seen last frame, used to notice when an enemy gets hit
+#[derive(Component, Default)]
+pub struct PreviousHealth(pub f32);
+
+/// A dying enemy's sprite left behind to play its death clip
+#[derive(Component)]
+#[require(LevelEntity)]
+pub struct DeathAnimation;
+
+fn animate_sprites(time: Res<Time>, mut sprites: Query<(&mut SpriteAnimation, &mut Sprite)>) {
+    for (mut animation, mut sprite) in sprites.iter_mut() {
+        if animation.finished {
+            continue;
+        }
+        let Some(clip) = animation.clips.get(&animation.current).copied() else { continue; };
+
+        animation.timer.tick(time.delta());
+        for _ in 0..animation.timer.times_finished_this_tick() {
+            if clip.first + animation.frame < clip.last {
+                animation.frame += 1;
+            } else if animation.current.loops() {
+                animation.frame = 0;
+            } else if animation.current == AnimationName::Die {
+                // Hold the last frame until the corpse is removed
+                animation.finished = true;
+                break;
+            } else {
+                let default_clip = animation.default_clip;
+                animation.restart(default_clip);
+                break;
+            }
+        }
+
+        if let (Some(index), Some(atlas)) = (animation.atlas_index(), sprite.texture_atlas.as_mut())
+            && atlas.index != index
+        {
+            atlas.index = index;
+        }
+    }
+}
+
+fn play_enemy_hit_animations(
+    mut enemies: Query<(&Enemy, &mut SpriteAnimation, &mut PreviousHealth)>,
+) {
+    for (enemy, mut animation, mut previous_health) in enemies.iter_mut() {
+        if enemy.health < previous_health.0 && enemy.health > 0.0 {
+            animation.play(AnimationName::Hit);
+        }
+        previous_health.0 = enemy.health;
+    }
+}
+
+/// Leaves a corpse playing the Die clip behind for enemies that are about to be removed
+pub fn play_death_animations(
+    mut commands: Commands,
+    enemies: Query<(&Enemy, &Transform, &Sprite, &SpriteAnimation)>,
+) {
+    for (enemy, transform, sprite, animation) in enemies.iter() {
+        if enemy.health > 0.0 || !animation.has_clip(AnimationName::Die) {
+            continue;
+        }
+        let mut corpse_animation = animation.clone();
+        corpse_animation.play(AnimationName::Die);
+        commands.spawn((sprite.clone(), *transform, corpse_animation, DeathAnimation));
+    }
+}
+
+fn despawn_finished_death_animations(
+    mut commands: Commands,
+    corpses: Query<(Entity, &SpriteAnimation), With<DeathAnimation>>,
+) {
+    for (entity, animation) in corpses.iter() {
+        if animation.is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
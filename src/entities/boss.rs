@@ -0,0 +1,133 @@
+use crate::core::game_state::GameState;
+use crate::core::map::Map;
+use crate::core::utils::is_point_in_radius;
+use crate::entities::ability::{shield_amount, Abilities, EnemyAbility};
+use crate::entities::damage::Resistances;
+use crate::entities::enemy::{spawn_enemy_at, Enemy, EnemyCatalog, EnemyType, SpawnModifiers};
+use crate::entities::tower::{Tower, TowerDisabled};
+use crate::levels::level::WaveMember;
+use bevy::math::Vec3Swizzles;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+pub struct BossPlugin;
+
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, advance_boss_phases.run_if(in_state(GameState::Playing)));
+    }
+}
+
+/// A stage of a boss fight, entered once health drops to `health_threshold`
+#[derive(Debug, Clone, Deserialize)]
+pub struct BossPhase {
+    pub health_threshold: f32, // Share of max health, 0.0 to 1.0
+    #[serde(default)]
+    pub speed: Option<f32>,
+    #[serde(default)]
+    pub sprite: Option<String>,
+    #[serde(default)]
+    pub resistances: Option<Resistances>,
+    #[serde(default)]
+    pub abilities: Option<Vec<EnemyAbility>>, // Replaces the boss's abilities
+    #[serde(default)]
+    pub effects: Vec<PhaseEffect>, // Triggered once when the phase starts
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind")]
+pub enum PhaseEffect {
+    DisableTowers { radius: f32, duration: f32 },    // Towers near the boss stop shooting
+    SummonWave { enemy_type: EnemyType, count: u32 }, // Sends extra enemies from the start
+}
+
+#[derive(Component)]
+pub struct Boss {
+    pub phases: Vec<BossPhase>,
+    pub next_phase: usize, // Index of the phase that hasn't started yet
+}
+
+impl Boss {
+    pub fn new(mut phases: Vec<BossPhase>) -> Self {
+        phases.sort_by(|a, b| b.health_threshold.total_cmp(&a.health_threshold));
+        Boss { phases, next_phase: 0 }
+    }
+
+    /// 1-based number of the phase the boss is currently in
+    pub fn phase_number(&self) -> usize {
+        self.next_phase + 1
+    }
+}
+
+fn advance_boss_phases(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    map: Res<Map>,
+    catalog: Res<EnemyCatalog>,
+    mut bosses: Query<(Entity, &Transform, &mut Enemy, &mut Boss, &mut Sprite, &mut Resistances, &mut Abilities)>,
+    wave_members: Query<&WaveMember>,
+    towers: Query<(Entity, &Transform), With<Tower>>,
+) {
+    for (entity, transform, mut enemy, mut boss, mut sprite, mut resistances, mut abilities) in bosses.iter_mut() {
+        if enemy.health <= 0.0 || enemy.max_health <= 0.0 {
+            continue;
+        }
+        let health_ratio = enemy.health / enemy.max_health;
+
+        // A big hit can skip straight through several phases
+        while let Some(phase) = boss.phases.get(boss.next_phase).cloned() {
+            if health_ratio > phase.health_threshold {
+                break;
+            }
+            boss.next_phase += 1;
+
+            if let Some(speed) = phase.speed {
+                enemy.speed = speed;
+            }
+            if let Some(image) = &phase.sprite {
+                sprite.image = asset_server.load(image);
+            }
+            if let Some(phase_resistances) = phase.resistances {
+                *resistances = phase_resistances;
+            }
+            if let Some(phase_abilities) = phase.abilities {
+                enemy.shield += shield_amount(&phase_abilities);
+                abilities.0 = phase_abilities;
+            }
+
+            let boss_pos = transform.translation.xy();
+            for effect in phase.effects.iter() {
+                match effect {
+                    PhaseEffect::DisableTowers { radius, duration } => {
+                        for (tower, tower_transform) in towers.iter() {
+                            if is_point_in_radius(boss_pos, tower_transform.translation.xy(), *radius) {
+                                commands.entity(tower).insert(TowerDisabled {
+                                    timer: Timer::from_seconds(*duration, TimerMode::Once),
+                                });
+                            }
+                        }
+                    }
+                    PhaseEffect::SummonWave { enemy_type, count } => {
+                        let start_pos = map.grid_to_world(map.spawn_point(0));
+                        for _ in 0..*count {
+                            let summoned = spawn_enemy_at(
+                                &mut commands,
+                                &asset_server,
+                                &catalog,
+                                enemy_type.clone(),
+                                start_pos,
+                                SpawnModifiers::default(),
+                            );
+                            // Summons belong to the boss's wave, which isn't cleared until they die
+                            if let (Some(summoned), Ok(wave)) = (summoned, wave_members.get(entity)) {
+                                commands.entity(summoned).insert(WaveMember(wave.0));
+                            }
+                        }
+                    }
+                }
+            }
+
+            info!("Boss {:?} entered phase {}", entity, boss.phase_number());
+        }
+    }
+}
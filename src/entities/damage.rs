@@ -0,0 +1,136 @@
+use crate::entities::enemy::Enemy;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+// Armor value at which half of the armor-affected damage is blocked
+const ARMOR_SCALE: f32 = 100.0;
+// Resistances can never block more than this share of a hit
+const MAX_RESISTANCE: f32 = 0.9;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+pub enum DamageType {
+    #[default]
+    Physical,  // Fully reduced by armor
+    Explosive, // Reduced by half the armor
+    Magic,     // Ignores armor
+    Pierce,    // Ignores armor
+}
+
+impl DamageType {
+    /// Share of the target's armor that applies to this damage type
+    fn armor_factor(&self) -> f32 {
+        match self {
+            DamageType::Physical => 1.0,
+            DamageType::Explosive => 0.5,
+            DamageType::Magic | DamageType::Pierce => 0.0,
+        }
+    }
+}
+
+/// Armor and per damage type resistances of an enemy
+#[derive(Component, Debug, Clone, Copy, Default, Deserialize)]
+#[serde(default)]
+pub struct Resistances {
+    pub armor: f32,
+    // Share of each damage type that is resisted, negative values are weaknesses
+    pub physical: f32,
+    pub explosive: f32,
+    pub magic: f32,
+    pub pierce: f32,
+}
+
+impl Resistances {
+    pub fn resistance(&self, damage_type: DamageType) -> f32 {
+        match damage_type {
+            DamageType::Physical => self.physical,
+            DamageType::Explosive => self.explosive,
+            DamageType::Magic => self.magic,
+            DamageType::Pierce => self.pierce,
+        }
+    }
+}
+
+/// Damage left after armor and resistances, used by every source of damage
+pub fn calculate_damage(amount: f32, damage_type: DamageType, resistances: &Resistances) -> f32 {
+    let armor = resistances.armor.max(0.0) * damage_type.armor_factor();
+    let armor_multiplier = ARMOR_SCALE / (ARMOR_SCALE + armor);
+    let resistance = resistances.resistance(damage_type).min(MAX_RESISTANCE);
+
+    (amount * armor_multiplier * (1.0 - resistance)).max(0.0)
+}
+
+/// Applies damage to an enemy and returns how much got through to its health
+pub fn deal_damage(
+    enemy: &mut Enemy,
+    resistances: &Resistances,
+    amount: f32,
+    damage_type: DamageType,
+) -> f32 {
+    let damage = calculate_damage(amount, damage_type, resistances);
+    // Shields soak damage before health is touched
+    let absorbed = damage.min(enemy.shield.max(0.0));
+    enemy.shield -= absorbed;
+    enemy.health -= damage - absorbed;
+    damage - absorbed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_close(actual: f32, expected: f32) {
+        assert!((actual - expected).abs() < 1e-4, "expected {}, got {}", expected, actual);
+    }
+
+    #[test]
+    fn no_armor_or_resistance_lets_everything_through() {
+        let resistances = Resistances::default();
+        for damage_type in [DamageType::Physical, DamageType::Explosive, DamageType::Magic, DamageType::Pierce] {
+            assert_close(calculate_damage(10.0, damage_type, &resistances), 10.0);
+        }
+    }
+
+    #[test]
+    fn armor_scales_by_damage_type() {
+        let resistances = Resistances { armor: ARMOR_SCALE, ..default() };
+
+        assert_close(calculate_damage(10.0, DamageType::Physical, &resistances), 5.0);
+        // Half the armor applies: 100 / (100 + 50)
+        assert_close(calculate_damage(12.0, DamageType::Explosive, &resistances), 8.0);
+        assert_close(calculate_damage(10.0, DamageType::Magic, &resistances), 10.0);
+        assert_close(calculate_damage(10.0, DamageType::Pierce, &resistances), 10.0);
+    }
+
+    #[test]
+    fn negative_armor_counts_as_none() {
+        let resistances = Resistances { armor: -50.0, ..default() };
+        assert_close(calculate_damage(10.0, DamageType::Physical, &resistances), 10.0);
+    }
+
+    #[test]
+    fn resistance_is_capped() {
+        let resistances = Resistances { physical: 0.5, magic: 5.0, ..default() };
+
+        assert_close(calculate_damage(10.0, DamageType::Physical, &resistances), 5.0);
+        assert_close(calculate_damage(10.0, DamageType::Magic, &resistances), 10.0 * (1.0 - MAX_RESISTANCE));
+    }
+
+    #[test]
+    fn negative_resistance_is_a_weakness() {
+        let resistances = Resistances { pierce: -0.5, ..default() };
+        assert_close(calculate_damage(10.0, DamageType::Pierce, &resistances), 15.0);
+    }
+
+    #[test]
+    fn armor_and_resistance_multiply() {
+        let resistances = Resistances { armor: ARMOR_SCALE, physical: 0.5, ..default() };
+        assert_close(calculate_damage(20.0, DamageType::Physical, &resistances), 5.0);
+    }
+
+    #[test]
+    fn zero_and_negative_amounts_deal_nothing() {
+        let resistances = Resistances { pierce: -0.5, ..default() };
+        assert_close(calculate_damage(0.0, DamageType::Pierce, &resistances), 0.0);
+        assert_close(calculate_damage(-10.0, DamageType::Pierce, &resistances), 0.0);
+    }
+}
@@ -1,19 +1,37 @@
 use crate::core::game_state::GameState;
 use crate::core::map::Map;
 use crate::core::utils::distance;
-use crate::entities::pathfinding::{FlowField, FlowDirection};
+use crate::entities::boss::{Boss, BossPhase};
+use crate::entities::ability::{shield_amount, trigger_death_abilities, Abilities, EnemyAbility};
+use crate::entities::animation::{
+    play_death_animations, sheet_sprite, AnimationClips, AnimationName, PreviousHealth, SpriteAnimation,
+    SpriteAtlas,
+};
+use crate::entities::damage::Resistances;
+use crate::entities::pathfinding::FlowField;
+use crate::entities::status_effect::StatusEffects;
+use crate::levels::level::{LevelEntity, WaveMember};
 use bevy::prelude::*;
 use bevy::math::Vec3Swizzles;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
 pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup_enemies)
+        app.add_event::<EnemyLeakedEvent>()
+            .init_resource::<LeakStats>()
+            .add_systems(Startup, setup_enemies)
             .add_systems(Update, (
                 move_enemies_along_path, 
+                separate_enemies,
+                play_death_animations,
                 check_enemy_health, 
-                handle_enemies_at_end)
+                handle_enemies_at_end,
+                record_leaks)
+                .chain()
                 .run_if(in_state(GameState::Playing))
             );
     
@@ -22,126 +40,362 @@ impl Plugin for EnemyPlugin {
 }
 
 #[derive(Component)]
+#[require(StatusEffects, Resistances, Abilities, LevelEntity)]
 pub struct Enemy {
     pub health: f32,
+    pub max_health: f32,
+    pub shield: f32, // Absorbs damage before health, see EnemyAbility::Shield
     pub speed: f32,
     pub reward: u32,
     pub enemy_type: EnemyType,
     pub path_index: usize,  // Current position on the path
     pub path_progress: f32, // Progress between current and next path point (0.0 to 1.0)
+    pub distance_to_goal: f32, // Tiles left along the route
+    pub leak_damage: u32,
+    pub leak_scales_with_health: bool,
+    pub movement: MovementMode,
+    pub lane_offset: f32, // Sideways offset from the path centre line, as a share of a tile
 }
 
-#[derive(Debug, Clone, Copy)]
+// Furthest an enemy's lane sits from the centre of the path, as a share of a tile
+const MAX_LANE_OFFSET: f32 = 0.3;
+// How fast enemies turn towards the flow direction, in radians per second
+const TURN_RATE: f32 = 8.0;
+// How strongly enemies steer back to their lane
+const LANE_STEERING: f32 = 2.0;
+// Enemies closer than this push each other apart
+const SEPARATION_RADIUS: f32 = 16.0;
+// Speed at which overlapping enemies are pushed apart
+const SEPARATION_SPEED: f32 = 40.0;
+
+/// How an enemy gets from the start to the end of the map
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+pub enum MovementMode {
+    #[default]
+    Ground, // Follows the flow field along path tiles
+    Air,    // Flies straight to the end, ignoring the path
+}
+
+impl Enemy {
+    /// Player health lost when this enemy reaches the end
+    pub fn damage_on_leak(&self) -> u32 {
+        if !self.leak_scales_with_health || self.max_health <= 0.0 {
+            return self.leak_damage;
+        }
+        // Badly hurt enemies still cost at least one health
+        let ratio = (self.health / self.max_health).clamp(0.0, 1.0);
+        ((self.leak_damage as f32 * ratio).ceil() as u32).max(1)
+    }
+}
+
+/// Sent when an enemy reaches the end of the path
+#[derive(Event)]
+pub struct EnemyLeakedEvent {
+    pub enemy_type: EnemyType,
+    pub damage: u32,
+    pub position: Vec2,
+}
+
+/// Which enemies got through during the current game
+#[derive(Resource, Default)]
+pub struct LeakStats {
+    pub by_type: HashMap<EnemyType, u32>,
+    pub health_lost: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(from = "String", into = "String")]
 pub enum EnemyType {
     Basic,
     Fast,
     Tank,
     Boss,
+    Custom(String), // Any other kind defined in assets/enemies.json
+}
+
+impl From<String> for EnemyType {
+    fn from(name: String) -> Self {
+        match name.as_str() {
+            "Basic" => EnemyType::Basic,
+            "Fast" => EnemyType::Fast,
+            "Tank" => EnemyType::Tank,
+            "Boss" => EnemyType::Boss,
+            _ => EnemyType::Custom(name),
+        }
+    }
+}
+
+impl From<EnemyType> for String {
+    fn from(enemy_type: EnemyType) -> Self {
+        enemy_type.name().to_string()
+    }
+}
+
+impl EnemyType {
+    pub fn name(&self) -> &str {
+        match self {
+            EnemyType::Basic => "Basic",
+            EnemyType::Fast => "Fast",
+            EnemyType::Tank => "Tank",
+            EnemyType::Boss => "Boss",
+            EnemyType::Custom(name) => name,
+        }
+    }
 }
 
-fn setup_enemies() {}
+/// Enemy definitions loaded from assets/enemies.json
+#[derive(Resource, Clone, Default, Deserialize)]
+pub struct EnemyCatalog {
+    pub enemies: Vec<EnemyDefinition>,
+}
 
-/// Spawns an enemy at the start of the path
-pub fn spawn_enemy(
+#[derive(Clone, Debug, Deserialize)]
+pub struct EnemyDefinition {
+    pub enemy_type: EnemyType,
+    pub health: f32,
+    pub speed: f32,
+    pub reward: u32,
+    #[serde(default = "default_leak_damage")]
+    pub leak_damage: u32, // Player health lost when this enemy reaches the end
+    #[serde(default)]
+    pub leak_scales_with_health: bool, // Scale leak damage by the health the enemy has left
+    #[serde(default)]
+    pub movement: MovementMode,
+    #[serde(default)]
+    pub abilities: Vec<EnemyAbility>,
+    #[serde(default)]
+    pub phases: Vec<BossPhase>, // Makes this enemy a boss, ordered by health threshold
+    pub sprite: String,
+    #[serde(default)]
+    pub atlas: Option<SpriteAtlas>,
+    #[serde(default)]
+    pub animations: AnimationClips, // Needs `atlas`, Walk is played by default
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+    #[serde(default)]
+    pub resistances: Resistances,
+    #[serde(skip)]
+    pub atlas_layout: Option<Handle<TextureAtlasLayout>>, // Built from `atlas` on load
+}
+
+fn default_leak_damage() -> u32 {
+    10
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+impl EnemyCatalog {
+    pub fn get(&self, enemy_type: &EnemyType) -> Option<&EnemyDefinition> {
+        self.enemies.iter().find(|enemy| enemy.enemy_type == *enemy_type)
+    }
+}
+
+fn setup_enemies(mut commands: Commands, mut texture_layouts: ResMut<Assets<TextureAtlasLayout>>) {
+    let mut catalog = std::fs::read_to_string("assets/enemies.json")
+        .map_err(|e| format!("Error reading enemy file: {}", e))
+        .and_then(|json_str| {
+            serde_json::from_str::<EnemyCatalog>(&json_str)
+                .map_err(|e| format!("Error parsing JSON: {}", e))
+        })
+        .unwrap_or_else(|e| {
+            error!("Failed to load enemy catalog: {}", e);
+            EnemyCatalog::default()
+        });
+
+    for definition in catalog.enemies.iter_mut() {
+        if let Some(atlas) = &definition.atlas {
+            definition.atlas_layout = Some(texture_layouts.add(atlas.layout()));
+        }
+    }
+
+    info!("Loaded {} enemy definitions", catalog.enemies.len());
+    commands.insert_resource(catalog);
+}
+
+/// Adjustments applied on top of an archetype's stats when spawning
+#[derive(Debug, Clone, Copy)]
+pub struct SpawnModifiers {
+    pub health: f32,
+    pub scale: f32,
+    pub reward: f32,
+}
+
+impl Default for SpawnModifiers {
+    fn default() -> Self {
+        SpawnModifiers {
+            health: 1.0,
+            scale: 1.0,
+            reward: 1.0,
+        }
+    }
+}
+
+/// Spawns an enemy anywhere on the map, e.g. minions where their boss died
+pub fn spawn_enemy_at(
     commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
-    map: &Res<Map>,
+    asset_server: &AssetServer,
+    catalog: &EnemyCatalog,
     enemy_type: EnemyType,
-) {
-    // Get start position from the map
-    let start_pos = map.grid_to_world(map.start);
-
-    // Determine enemy properties based on type
-    let (health, speed, reward) = match enemy_type {
-        EnemyType::Basic => (100.0, 100.0, 10),
-        EnemyType::Fast => (50.0, 100.0, 15),
-        EnemyType::Tank => (200.0, 30.0, 20),
-        EnemyType::Boss => (500.0, 40.0, 50),
+    position: Vec2,
+    modifiers: SpawnModifiers,
+) -> Option<Entity> {
+    let Some(definition) = catalog.get(&enemy_type) else {
+        warn!("No enemy definition for {:?}", enemy_type);
+        return None;
     };
 
-    // Load appropriate sprite based on enemy type
-    let texture_path = match enemy_type {
-        EnemyType::Basic => "enemies/basic_enemy.png",
-        EnemyType::Fast => "enemies/fast_enemy.png",
-        EnemyType::Tank => "enemies/tank_enemy.png",
-        EnemyType::Boss => "enemies/boss_enemy.png",
-    };
+    let health = definition.health * modifiers.health;
 
     // Spawn the enemy entity
-    commands.spawn((
-        Sprite {
-            image: asset_server.load(texture_path),
-            ..default()
-        },
-        Transform::from_translation(Vec3::new(start_pos.x, start_pos.y, 1.0)),
-        Enemy {
-            health,
-            speed,
-            reward,
-            enemy_type,
-            path_index: 0, // Start at the beginning of the path
-            path_progress: 0.0,
-        },
-    ));
+    let mut entity_commands = commands.spawn((
+            sheet_sprite(
+                asset_server.load(&definition.sprite),
+                definition.atlas.as_ref(),
+                definition.atlas_layout.clone(),
+            ),
+            Transform::from_translation(Vec3::new(position.x, position.y, 1.0))
+                .with_scale(Vec3::splat(definition.scale * modifiers.scale)),
+            Enemy {
+                health,
+                max_health: health,
+                shield: shield_amount(&definition.abilities),
+                speed: definition.speed,
+                reward: (definition.reward as f32 * modifiers.reward).round() as u32,
+                enemy_type,
+                path_index: 0, // Start at the beginning of the path
+                path_progress: 0.0,
+                distance_to_goal: f32::MAX,
+                leak_damage: definition.leak_damage,
+                leak_scales_with_health: definition.leak_scales_with_health,
+                movement: definition.movement,
+                lane_offset: rand::rng().random_range(-MAX_LANE_OFFSET..=MAX_LANE_OFFSET),
+            },
+            definition.resistances,
+            Abilities(definition.abilities.clone()),
+        ));
+
+    if definition.atlas_layout.is_some()
+        && let Some(animation) = SpriteAnimation::new(&definition.animations, AnimationName::Walk)
+    {
+        entity_commands.insert((animation, PreviousHealth::default()));
+    }
+
+    if !definition.phases.is_empty() {
+        entity_commands.insert(Boss::new(definition.phases.clone()));
+    }
+
+    Some(entity_commands.id())
 }
 
 fn move_enemies_along_path(
     time: Res<Time>,
     map: Res<Map>,
     flow_field: Option<Res<FlowField>>,
-    mut enemies: Query<(&mut Transform, &Enemy)>, // Keep Enemy non-mut if only reading speed
+    mut enemies: Query<(&mut Transform, &mut Enemy, &StatusEffects)>,
 ) {
     let delta = time.delta_secs();
 
     let Some(flow_field) = flow_field else { return; };
     if !flow_field.is_initialized { return; }
 
-    for (mut transform, enemy) in enemies.iter_mut() {
+    let tile_size = map.grid_size.x.max(1.0);
+    // Progress is measured against the longest route from any spawn portal
+    let air_route_length = map
+        .starts
+        .iter()
+        .map(|&start| {
+            let start_pos_world = map.grid_to_world(start);
+            start_pos_world.distance(map.nearest_end(start_pos_world)) / tile_size
+        })
+        .fold(0.0, f32::max);
+    let ground_route_length = map
+        .starts
+        .iter()
+        .filter_map(|&start| flow_field.tiles_to_goal(start))
+        .reduce(f32::max)
+        .unwrap_or(air_route_length);
+
+    for (mut transform, mut enemy, effects) in enemies.iter_mut() {
         let current_pos_world = transform.translation.xy();
 
-        let current_grid_pos = map.world_to_grid(current_pos_world);
-
-        if current_grid_pos.x >= flow_field.width as u32 || current_grid_pos.y >= flow_field.height as u32 {
-             warn!(
-                "Enemy at {:?} (world {:?}) is outside flow field bounds ({}, {}). Stopping.",
-                current_grid_pos, current_pos_world, flow_field.width, flow_field.height
-            );
-             continue;
-        }
-
-        let flow_direction_enum = flow_field.get_direction(current_grid_pos.x as usize, current_grid_pos.y as usize);
-
-        let Some(direction) = flow_direction_enum else { continue; };
-        if direction == FlowDirection::None { continue; };
-
-        let next_grid_pos = match direction {
-            FlowDirection::North => UVec2::new(current_grid_pos.x, current_grid_pos.y.saturating_sub(1)), // Grid Y decreases upwards
-            FlowDirection::South => UVec2::new(current_grid_pos.x, (current_grid_pos.y + 1).min(flow_field.height as u32 - 1)), // Grid Y increases downwards
-            FlowDirection::East => UVec2::new((current_grid_pos.x + 1).min(flow_field.width as u32 - 1), current_grid_pos.y),
-            FlowDirection::West => UVec2::new(current_grid_pos.x.saturating_sub(1), current_grid_pos.y),
-            FlowDirection::None => current_grid_pos, // Should not happen due to check above
+        let (desired_direction, distance_to_goal, route_length) = match enemy.movement {
+            MovementMode::Air => {
+                let end_pos_world = map.nearest_end(current_pos_world);
+                (
+                    (end_pos_world - current_pos_world).normalize_or_zero(),
+                    current_pos_world.distance(end_pos_world) / tile_size,
+                    air_route_length,
+                )
+            }
+            MovementMode::Ground => {
+                let current_grid_pos = map.world_to_grid(current_pos_world);
+                let Some(tiles_left) = flow_field.tiles_to_goal(current_grid_pos) else {
+                    // Off the flow field, put it back on the nearest tile that leads to an exit
+                    match flow_field.nearest_reachable_tile(&map, current_pos_world) {
+                        Some(tile) => {
+                            let snapped = map.grid_to_world(tile);
+                            transform.translation.x = snapped.x;
+                            transform.translation.y = snapped.y;
+                        }
+                        None => warn!(
+                            "Enemy at {:?} (world {:?}) is not on the flow field and no path tile leads to an exit.",
+                            current_grid_pos, current_pos_world
+                        ),
+                    }
+                    continue;
+                };
+
+                let cell_centre = map.grid_to_world(current_grid_pos);
+                let cell_direction = flow_field
+                    .get_direction(current_grid_pos.x as usize, current_grid_pos.y as usize)
+                    .map(|direction| direction.to_vec2())
+                    .unwrap_or(Vec2::ZERO);
+
+                let flow = flow_field.sample(&map, current_pos_world);
+                let desired = if flow == Vec2::ZERO {
+                    // On the goal tile there is no flow left, head for its centre
+                    (cell_centre - current_pos_world).normalize_or_zero()
+                } else {
+                    // Steer back towards this enemy's lane so turns don't drift it off
+                    let side = flow.perp();
+                    let lane_error = (current_pos_world - cell_centre).dot(side) / tile_size - enemy.lane_offset;
+                    (flow - side * lane_error * LANE_STEERING).normalize_or_zero()
+                };
+
+                // Sub-tile position along the current cell's flow direction
+                let into_cell = (current_pos_world - cell_centre).dot(cell_direction) / tile_size;
+                (desired, (tiles_left - into_cell).max(0.0), ground_route_length)
+            }
         };
 
-        let target_pos_world = map.grid_to_world(next_grid_pos);
-
-        let direction_to_target = target_pos_world - current_pos_world;
+        if desired_direction == Vec2::ZERO {
+            continue;
+        }
 
-        let max_distance_this_frame = enemy.speed * delta;
+        // Turn gradually instead of snapping to the new direction at corners
+        let current_heading = (transform.rotation * Vec3::X).xy();
+        let heading = rotate_towards(current_heading, desired_direction, TURN_RATE * delta);
 
-        let movement;
-        if direction_to_target.length_squared() < max_distance_this_frame * max_distance_this_frame {
-            movement = direction_to_target;
-        } else {
-            movement = direction_to_target.normalize_or_zero() * max_distance_this_frame;
+        let movement = heading * enemy.speed * effects.speed_multiplier() * delta;
+        let mut new_pos = current_pos_world;
+        for axis_step in [Vec2::new(movement.x, 0.0), Vec2::new(0.0, movement.y)] {
+            let candidate = new_pos + axis_step;
+            if enemy.movement == MovementMode::Ground && !map.is_path(map.world_to_grid(candidate)) {
+                continue;
+            }
+            new_pos = candidate;
         }
 
-        transform.translation.x += movement.x;
-        transform.translation.y += movement.y;
+        transform.translation.x = new_pos.x;
+        transform.translation.y = new_pos.y;
+        transform.rotation = Quat::from_rotation_z(heading.to_angle());
 
-        if movement != Vec2::ZERO {
-            let angle = movement.y.atan2(movement.x);
-            transform.rotation = Quat::from_rotation_z(angle);
-        }
+        enemy.distance_to_goal = distance_to_goal;
+        let travelled = (route_length - distance_to_goal).max(0.0);
+        enemy.path_index = travelled.floor() as usize;
+        enemy.path_progress = travelled.fract();
 
         // --- Debug Visualization (Optional) ---
         // use bevy::gizmos::gizmos::Gizmos;
@@ -154,6 +408,83 @@ fn move_enemies_along_path(
         // ------------------------------------
     }
 }
+// Pushes overlapping enemies apart, keeping ground enemies on path tiles
+fn separate_enemies(
+    time: Res<Time>,
+    map: Res<Map>,
+    mut enemies: Query<(Entity, &mut Transform, &Enemy)>,
+) {
+    let delta = time.delta_secs();
+
+    // Bucket enemies by SEPARATION_RADIUS sized cells so only neighbours are compared
+    let bucket_of = |pos: Vec2| (pos / SEPARATION_RADIUS).floor().as_ivec2();
+    let mut buckets: HashMap<IVec2, Vec<(Entity, Vec2, MovementMode)>> = HashMap::new();
+    for (entity, transform, enemy) in enemies.iter() {
+        let pos = transform.translation.xy();
+        buckets.entry(bucket_of(pos)).or_default().push((entity, pos, enemy.movement));
+    }
+
+    for (entity, mut transform, enemy) in enemies.iter_mut() {
+        let pos = transform.translation.xy();
+        let bucket = bucket_of(pos);
+        let mut push = Vec2::ZERO;
+
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                let Some(neighbours) = buckets.get(&(bucket + IVec2::new(dx, dy))) else { continue; };
+                for &(other, other_pos, movement) in neighbours {
+                    // Flyers and walkers pass over each other
+                    if other == entity || movement != enemy.movement {
+                        continue;
+                    }
+                    let offset = pos - other_pos;
+                    let distance = offset.length();
+                    if distance >= SEPARATION_RADIUS {
+                        continue;
+                    }
+                    // Enemies on the exact same spot split along a fixed per-entity direction
+                    let away = if distance > f32::EPSILON {
+                        offset / distance
+                    } else {
+                        Vec2::from_angle(entity.index() as f32)
+                    };
+                    push += away * (1.0 - distance / SEPARATION_RADIUS);
+                }
+            }
+        }
+
+        if push == Vec2::ZERO {
+            continue;
+        }
+
+        let step = push.clamp_length_max(1.0) * SEPARATION_SPEED * delta;
+        // Move each axis separately so enemies can still slide along the path edge
+        let mut new_pos = pos;
+        for axis_step in [Vec2::new(step.x, 0.0), Vec2::new(0.0, step.y)] {
+            let candidate = new_pos + axis_step;
+            if enemy.movement == MovementMode::Ground && !map.is_path(map.world_to_grid(candidate)) {
+                continue;
+            }
+            new_pos = candidate;
+        }
+        transform.translation.x = new_pos.x;
+        transform.translation.y = new_pos.y;
+    }
+}
+
+// Rotates `from` towards `to` by at most `max_angle` radians
+fn rotate_towards(from: Vec2, to: Vec2, max_angle: f32) -> Vec2 {
+    if from == Vec2::ZERO {
+        return to;
+    }
+    let angle = from.angle_to(to);
+    if angle.abs() <= max_angle {
+        to
+    } else {
+        Vec2::from_angle(max_angle.copysign(angle)).rotate(from)
+    }
+}
+
 // fn move_enemies_along_path(
 //     time: Res<Time>,
 //     map: Res<Map>,
@@ -226,15 +557,34 @@ fn move_enemies_along_path(
 
 fn check_enemy_health(
     mut commands: Commands,
-    mut enemies: Query<(Entity, &Enemy)>,
+    asset_server: Res<AssetServer>,
+    map: Res<Map>,
+    catalog: Res<EnemyCatalog>,
+    enemies: Query<(Entity, &Transform, &Enemy, &Abilities, Option<&WaveMember>)>,
     mut game_resources: Option<ResMut<crate::core::game_state::PlayerResource>>,
 ) {
-    for (entity, enemy) in enemies.iter() {
+    for (entity, transform, enemy, abilities, wave) in enemies.iter() {
         // Check if enemy is dead
         if enemy.health <= 0.0 {
             // Despawn the enemy
             commands.entity(entity).despawn();
 
+            let spawned = trigger_death_abilities(
+                &mut commands,
+                &asset_server,
+                &map,
+                &catalog,
+                enemy,
+                abilities,
+                transform.translation.xy(),
+            );
+            // What an enemy leaves behind still counts towards its wave
+            if let Some(wave) = wave {
+                for spawned in spawned {
+                    commands.entity(spawned).insert(WaveMember(wave.0));
+                }
+            }
+
             // Award money/score to player if resources exist
             if let Some(mut resources) = game_resources.as_mut() {
                 resources.money += enemy.reward;
@@ -247,21 +597,20 @@ fn check_enemy_health(
 fn handle_enemies_at_end(
     mut commands: Commands,
     map: Res<Map>,
-    enemies: Query<(Entity, &Transform, &Enemy)>, // Enemy component not strictly needed here
+    enemies: Query<(Entity, &Transform, &Enemy)>,
     mut game_resources: Option<ResMut<crate::core::game_state::PlayerResource>>,
+    mut leak_events: EventWriter<EnemyLeakedEvent>,
     time: Res<Time> // Add Time resource if you want to log frequency
 ) {
-    // Calculate the end position ONCE outside the loop
-    let end_pos_world = map.grid_to_world(map.end);
-
     // Define a threshold distance. This should likely be related to the grid size
     // or the enemy's speed/size. Maybe half a grid cell?
     let end_threshold = map.grid_size.x.min(map.grid_size.y) * 0.5; // Example threshold
 
-    for (entity, transform, _enemy) in enemies.iter() {
+    for (entity, transform, enemy) in enemies.iter() {
         let enemy_pos_xy = transform.translation.xy();
 
-        // Use distance_squared for efficiency if possible
+        // Whichever exit is closest, every one of them leaks
+        let end_pos_world = map.nearest_end(enemy_pos_xy);
         let distance_sq_to_end = enemy_pos_xy.distance_squared(end_pos_world);
 
         // If enemy is close enough to the end point
@@ -272,52 +621,25 @@ fn handle_enemies_at_end(
             // Despawn the enemy
             commands.entity(entity).despawn();
 
+            let damage = enemy.damage_on_leak();
+            leak_events.send(EnemyLeakedEvent {
+                enemy_type: enemy.enemy_type.clone(),
+                damage,
+                position: enemy_pos_xy,
+            });
+
             // Reduce player lives if resources exist
             if let Some(mut resources) = game_resources.as_mut() {
-                resources.health = resources.health.saturating_sub(10);
+                resources.health = resources.health.saturating_sub(damage);
                 info!(tick = time.elapsed_secs_f64(), "Player health remaining: {}", resources.health);
             }
         }
     }
 }
 
-// System for enemy wave spawning
-pub fn spawn_enemy_wave(
-    mut commands: Commands,
-    asset_server: Res<AssetServer>,
-    map: Res<Map>,
-    wave_data: Vec<(EnemyType, u32)>, // (enemy_type, count)
-    spawn_interval: f32,
-    time: Res<Time>,
-) -> impl FnMut() {
-    // Create a flat vec of all enemies to spawn
-    let mut enemies_to_spawn = Vec::new();
-    for (enemy_type, count) in wave_data {
-        for _ in 0..count {
-            enemies_to_spawn.push(enemy_type);
-        }
-    }
-
-    let mut spawn_timer = Timer::from_seconds(spawn_interval, TimerMode::Repeating);
-    let mut current_index = 0;
-
-
-        move || {
-            if current_index >= enemies_to_spawn.len() {
-                // Wave complete
-                info!("wave completed");
-                // return true;
-            }
-
-        spawn_timer.tick(time.delta());
-
-        if spawn_timer.just_finished() {
-            let enemy_type = enemies_to_spawn[current_index];
-            spawn_enemy(&mut commands, &asset_server, &map, enemy_type);
-            current_index += 1;
-        }
-
-        info!("wave not complete");
-        // false // Wave not complete yet
+fn record_leaks(mut leak_events: EventReader<EnemyLeakedEvent>, mut stats: ResMut<LeakStats>) {
+    for event in leak_events.read() {
+        *stats.by_type.entry(event.enemy_type.clone()).or_default() += 1;
+        stats.health_lost += event.damage;
     }
 }
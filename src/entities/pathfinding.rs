@@ -11,6 +11,11 @@ impl Plugin for PathfindingPlugin {
             .add_systems(Update, toggle_flow_field_visualization);
     }
 }
+// Integration cost of moving one tile
+const STEP_COST: u32 = 10;
+// Cells further apart than this along the route aren't blended together
+const MAX_BLEND_TILES: f32 = 2.0;
+
 #[derive(Resource, Default)]
 pub struct FlowFieldDebugConfig {
     pub show_visualization: bool,
@@ -83,7 +88,8 @@ impl FlowField {
         (x, y)
     }
 
-    pub fn compute(&mut self, map: &Map, goal_pos: UVec2) {
+    /// Fills the field from every goal at once, so each cell flows to its nearest exit
+    pub fn compute(&mut self, map: &Map, goals: &[UVec2]) {
         // Reset fields
         self.integration_field = vec![u32::MAX; self.width * self.height];
         self.field = vec![None; self.width * self.height];
@@ -91,32 +97,27 @@ impl FlowField {
         // Initialize a queue for Dijkstra's algorithm
         let mut queue = VecDeque::new();
 
-        // Check if goal position is valid
-        let goal_x = goal_pos.x as usize;
-        let goal_y = goal_pos.y as usize;
-
-        if goal_x >= self.width || goal_y >= self.height {
-            error!(
-                "Goal position ({}, {}) is out of bounds ({}, {})",
-                goal_x, goal_y, self.width, self.height
-            );
-            return;
-        }
-
-        // Convert goal to index
-        let goal_index = self.get_index(goal_x, goal_y);
+        for &goal_pos in goals {
+            let goal_x = goal_pos.x as usize;
+            let goal_y = goal_pos.y as usize;
 
-        // Set goal distance to 0 and add to queue
-        self.integration_field[goal_index] = 0;
-        queue.push_back(goal_index);
+            if goal_x >= self.width || goal_y >= self.height {
+                error!(
+                    "Goal position ({}, {}) is out of bounds ({}, {})",
+                    goal_x, goal_y, self.width, self.height
+                );
+                continue;
+            }
 
-        // Debug info about path
-        info!("Computing flow field from goal {:?} to start", goal_pos);
-        info!("Path has {} tiles", map.path_tiles.len());
+            // Every goal starts at distance 0
+            let goal_index = self.get_index(goal_x, goal_y);
+            self.integration_field[goal_index] = 0;
+            queue.push_back(goal_index);
+        }
 
-        // Print out the path tiles for debugging
-        for (i, &pos) in map.path_tiles.iter().enumerate() {
-            info!("Path tile {}: {:?}", i, pos);
+        if queue.is_empty() {
+            error!("Flow field has no valid goals");
+            return;
         }
 
         // Process the queue (Dijkstra's algorithm)
@@ -126,10 +127,10 @@ impl FlowField {
 
             // Define potential neighbors
             let neighbors = [
-                (x + 1, y, 10),             // East
-                (x.wrapping_sub(1), y, 10), // West
-                (x, y + 1, 10),             // South (in grid coordinates)
-                (x, y.wrapping_sub(1), 10), // North (in grid coordinates)
+                (x + 1, y, STEP_COST),             // East
+                (x.wrapping_sub(1), y, STEP_COST), // West
+                (x, y + 1, STEP_COST),             // South (in grid coordinates)
+                (x, y.wrapping_sub(1), STEP_COST), // North (in grid coordinates)
             ];
 
             for (nx, ny, cost) in neighbors {
@@ -141,7 +142,7 @@ impl FlowField {
                 let neighbor_index = self.get_index(nx, ny);
 
                 // Consider a cell walkable if it's in the path tiles
-                let is_walkable = map.path_tiles.contains(&UVec2::new(nx as u32, ny as u32));
+                let is_walkable = map.is_path(UVec2::new(nx as u32, ny as u32));
 
                 // Skip non-walkable cells
                 if !is_walkable {
@@ -172,7 +173,6 @@ impl FlowField {
 
             // Skip unreachable cells
             if self.integration_field[index] == u32::MAX {
-                info!("Path tile {:?} is not reachable!", path_pos);
                 continue;
             }
 
@@ -197,7 +197,7 @@ impl FlowField {
                 let neighbor_index = self.get_index(nx, ny);
 
                 // Skip non-path or unreachable cells
-                if !map.path_tiles.contains(&UVec2::new(nx as u32, ny as u32))
+                if !map.is_path(UVec2::new(nx as u32, ny as u32))
                     || self.integration_field[neighbor_index] == u32::MAX
                 {
                     continue;
@@ -214,27 +214,80 @@ impl FlowField {
             self.field[index] = Some(best_direction);
         }
 
-        // Debug output to verify directions
-        for &path_pos in &map.path_tiles {
-            let x = path_pos.x as usize;
-            let y = path_pos.y as usize;
+        self.is_initialized = true;
+    }
 
-            if x >= self.width || y >= self.height {
-                continue;
-            }
+    /// Path length from a cell to the goal in tiles, if the goal can be reached from it
+    pub fn tiles_to_goal(&self, grid_pos: UVec2) -> Option<f32> {
+        let (x, y) = (grid_pos.x as usize, grid_pos.y as usize);
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let cost = self.integration_field[self.get_index(x, y)];
+        (cost != u32::MAX).then(|| cost as f32 / STEP_COST as f32)
+    }
 
-            let index = self.get_index(x, y);
+    /// Closest path tile to a world position that can still reach the goal
+    pub fn nearest_reachable_tile(&self, map: &Map, world_pos: Vec2) -> Option<UVec2> {
+        map.path_tiles
+            .iter()
+            .copied()
+            .filter(|&tile| self.tiles_to_goal(tile).is_some())
+            .min_by(|&a, &b| {
+                let distance_a = map.grid_to_world(a).distance_squared(world_pos);
+                let distance_b = map.grid_to_world(b).distance_squared(world_pos);
+                distance_a.total_cmp(&distance_b)
+            })
+    }
 
-            if index < self.field.len() {
-                if let Some(dir) = self.field[index] {
-                    info!("Path tile at {:?} has direction: {:?}", path_pos, dir);
-                } else {
-                    info!("Path tile at {:?} has NO direction", path_pos);
-                }
+    /// Flow direction at a world position, blended between the four nearest cell centres
+    pub fn sample(&self, map: &Map, world_pos: Vec2) -> Vec2 {
+        let cell = map.world_to_grid(world_pos);
+        let Some(cell_cost) = self.tiles_to_goal(cell) else {
+            return Vec2::ZERO;
+        };
+        let centre = map.grid_to_world(cell);
+
+        // Offset from the cell centre in grid units, grid y points down
+        let offset = Vec2::new(
+            (world_pos.x - centre.x) / map.grid_size.x,
+            (centre.y - world_pos.y) / map.grid_size.y,
+        );
+        let x0 = cell.x as i32 - (offset.x < 0.0) as i32;
+        let y0 = cell.y as i32 - (offset.y < 0.0) as i32;
+        let tx = offset.x - offset.x.floor();
+        let ty = offset.y - offset.y.floor();
+
+        let mut blended = Vec2::ZERO;
+        let mut total_weight = 0.0;
+        for (dx, dy, weight) in [
+            (0, 0, (1.0 - tx) * (1.0 - ty)),
+            (1, 0, tx * (1.0 - ty)),
+            (0, 1, (1.0 - tx) * ty),
+            (1, 1, tx * ty),
+        ] {
+            let (x, y) = (x0 + dx, y0 + dy);
+            if x < 0 || y < 0 {
+                continue;
+            }
+            let neighbour = UVec2::new(x as u32, y as u32);
+            // Skip cells from another stretch of the path running alongside this one
+            let Some(cost) = self.tiles_to_goal(neighbour) else { continue; };
+            if (cost - cell_cost).abs() > MAX_BLEND_TILES {
+                continue;
             }
+            let Some(direction) = self.get_direction(neighbour.x as usize, neighbour.y as usize) else {
+                continue;
+            };
+            blended += direction.to_vec2() * weight;
+            total_weight += weight;
         }
 
-        self.is_initialized = true;
+        if total_weight <= 0.0 {
+            Vec2::ZERO
+        } else {
+            blended.normalize_or_zero()
+        }
     }
 
     /// Get flow direction at given grid coordinates
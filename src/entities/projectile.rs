@@ -1,22 +1,49 @@
+use crate::core::game_state::GameState;
+use crate::core::utils::{distance, is_point_in_radius};
+use crate::entities::damage::{deal_damage, DamageType, Resistances};
+use crate::entities::enemy::{Enemy, MovementMode};
+use crate::entities::status_effect::{StatusEffect, StatusEffects};
+use crate::entities::tower::{TargetLayers, TowerDefinition};
+use crate::levels::level::LevelEntity;
+use bevy::math::Vec3Swizzles;
 use bevy::prelude::*;
+use serde::Deserialize;
 
 pub struct ProjectilePlugin;
 
 impl Plugin for ProjectilePlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (move_projectiles, handle_projectile_collisions));
+        app.add_systems(
+            Update,
+            (move_projectiles, handle_projectile_collisions, fade_laser_beams)
+                .chain()
+                .run_if(in_state(GameState::Playing)),
+        );
     }
 }
 
+// Distance at which a projectile counts as having hit its target
+const HIT_RADIUS: f32 = 8.0;
+// How far a homing projectile looks for a new target when its own dies
+const RETARGET_RADIUS: f32 = 150.0;
+const MISSILE_ACCELERATION: f32 = 600.0;
+const AREA_EFFECT_RADIUS: f32 = 60.0;
+const LASER_BEAM_DURATION: f32 = 0.1;
+
 #[derive(Component)]
+#[require(LevelEntity)]
 pub struct Projectile {
     pub damage: f32,
+    pub damage_type: DamageType,
     pub speed: f32,
     pub target: Entity,
     pub projectile_type: ProjectileType,
+    pub target_position: Vec2, // Last known position of the target
+    pub effect: Option<StatusEffect>,
+    pub targets: TargetLayers, // Copied from the tower, limits what it can re-home onto
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, Deserialize)]
 pub enum ProjectileType {
     Bullet,
     Missile,
@@ -24,11 +51,226 @@ pub enum ProjectileType {
     AreaEffect,
 }
 
-fn move_projectiles() {
-    // Move projectiles toward their targets
+impl ProjectileType {
+    /// Starting speed in world units per second
+    pub fn initial_speed(&self) -> f32 {
+        match self {
+            ProjectileType::Bullet => 400.0,
+            ProjectileType::Missile => 100.0,
+            ProjectileType::Laser => 0.0,
+            ProjectileType::AreaEffect => 250.0,
+        }
+    }
+
+    fn sprite(&self) -> Sprite {
+        let (color, size) = match self {
+            ProjectileType::Bullet => (Color::srgb(1.0, 0.9, 0.3), Vec2::new(6.0, 6.0)),
+            ProjectileType::Missile => (Color::srgb(1.0, 0.5, 0.1), Vec2::new(12.0, 4.0)),
+            ProjectileType::Laser => (Color::srgb(1.0, 0.1, 0.1), Vec2::new(1.0, 3.0)),
+            ProjectileType::AreaEffect => (Color::srgb(0.2, 0.2, 0.2), Vec2::new(10.0, 10.0)),
+        };
+        Sprite {
+            color,
+            custom_size: Some(size),
+            ..default()
+        }
+    }
+}
+
+/// Short-lived visual left behind by an instant-hit laser
+#[derive(Component)]
+#[require(LevelEntity)]
+pub struct LaserBeam {
+    pub timer: Timer,
 }
 
-fn handle_projectile_collisions() {
-    // Detect and handle collisions between projectiles and enemies
+/// Spawns a projectile fired from `origin` at `target`, shaped by the firing tower's definition
+pub fn spawn_projectile(
+    commands: &mut Commands,
+    origin: Vec2,
+    target: Entity,
+    target_position: Vec2,
+    damage: f32,
+    tower: &TowerDefinition,
+) {
+    let projectile_type = tower.projectile_type;
+    commands.spawn((
+        projectile_type.sprite(),
+        Transform::from_translation(Vec3::new(origin.x, origin.y, 15.0)),
+        Projectile {
+            damage,
+            damage_type: tower.damage_type,
+            speed: projectile_type.initial_speed(),
+            target,
+            projectile_type,
+            target_position,
+            effect: tower.effect,
+            targets: tower.targets,
+        },
+    ));
 }
 
+fn move_projectiles(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut projectiles: Query<(Entity, &mut Transform, &mut Projectile)>,
+    enemies: Query<(Entity, &Transform, &Enemy), Without<Projectile>>,
+) {
+    let delta = time.delta_secs();
+
+    for (entity, mut transform, mut projectile) in projectiles.iter_mut() {
+        let position = transform.translation.xy();
+
+        if let Ok((_, enemy_transform, _)) = enemies.get(projectile.target) {
+            projectile.target_position = enemy_transform.translation.xy();
+        } else {
+            match projectile.projectile_type {
+                ProjectileType::Bullet | ProjectileType::Missile => {
+                    // Target is gone, home in on the nearest enemy or fizzle out
+                    let new_target = enemies
+                        .iter()
+                        .filter(|(_, t, enemy)| {
+                            enemy.health > 0.0
+                                && projectile.targets.can_target(enemy.movement)
+                                && is_point_in_radius(position, t.translation.xy(), RETARGET_RADIUS)
+                        })
+                        .min_by(|(_, a, _), (_, b, _)| {
+                            distance(position, a.translation.xy())
+                                .total_cmp(&distance(position, b.translation.xy()))
+                        });
+
+                    let Some((new_entity, new_transform, _)) = new_target else {
+                        commands.entity(entity).despawn();
+                        continue;
+                    };
+                    projectile.target = new_entity;
+                    projectile.target_position = new_transform.translation.xy();
+                }
+                // Shells land where the target was, lasers have already hit
+                ProjectileType::AreaEffect | ProjectileType::Laser => {}
+            }
+        }
+
+        if let ProjectileType::Laser = projectile.projectile_type {
+            continue;
+        }
+
+        if let ProjectileType::Missile = projectile.projectile_type {
+            projectile.speed += MISSILE_ACCELERATION * delta;
+        }
+
+        let to_target = projectile.target_position - position;
+        let max_distance_this_frame = projectile.speed * delta;
+
+        let movement = if to_target.length_squared() < max_distance_this_frame * max_distance_this_frame {
+            to_target
+        } else {
+            to_target.normalize_or_zero() * max_distance_this_frame
+        };
+
+        transform.translation.x += movement.x;
+        transform.translation.y += movement.y;
+
+        if movement != Vec2::ZERO {
+            transform.rotation = Quat::from_rotation_z(movement.y.atan2(movement.x));
+        }
+    }
+}
+
+fn handle_projectile_collisions(
+    mut commands: Commands,
+    projectiles: Query<(Entity, &Transform, &Projectile)>,
+    mut enemies: Query<
+        (Entity, &Transform, &mut Enemy, &Resistances, &mut StatusEffects),
+        Without<Projectile>,
+    >,
+) {
+    for (entity, transform, projectile) in projectiles.iter() {
+        let position = transform.translation.xy();
+
+        match projectile.projectile_type {
+            ProjectileType::Laser => {
+                if let Ok((_, _, mut enemy, resistances, mut effects)) = enemies.get_mut(projectile.target) {
+                    apply_hit(projectile, &mut enemy, resistances, &mut effects);
+                }
+                spawn_laser_beam(&mut commands, position, projectile.target_position);
+                commands.entity(entity).despawn();
+            }
+            ProjectileType::Bullet | ProjectileType::Missile => {
+                let Ok((_, enemy_transform, mut enemy, resistances, mut effects)) =
+                    enemies.get_mut(projectile.target)
+                else {
+                    continue;
+                };
+                if is_point_in_radius(position, enemy_transform.translation.xy(), HIT_RADIUS) {
+                    apply_hit(projectile, &mut enemy, resistances, &mut effects);
+                    commands.entity(entity).despawn();
+                }
+            }
+            ProjectileType::AreaEffect => {
+                if !is_point_in_radius(position, projectile.target_position, HIT_RADIUS) {
+                    continue;
+                }
+                // The blast only reaches enemies moving the same way as the target
+                let layer = enemies
+                    .get(projectile.target)
+                    .map(|(_, _, enemy, _, _)| enemy.movement)
+                    .unwrap_or(MovementMode::Ground);
+                for (_, enemy_transform, mut enemy, resistances, mut effects) in enemies.iter_mut() {
+                    if enemy.movement == layer
+                        && is_point_in_radius(position, enemy_transform.translation.xy(), AREA_EFFECT_RADIUS)
+                    {
+                        apply_hit(projectile, &mut enemy, resistances, &mut effects);
+                    }
+                }
+                commands.entity(entity).despawn();
+            }
+        }
+    }
+}
+
+fn apply_hit(
+    projectile: &Projectile,
+    enemy: &mut Enemy,
+    resistances: &Resistances,
+    effects: &mut StatusEffects,
+) {
+    deal_damage(enemy, resistances, projectile.damage, projectile.damage_type);
+    if let Some(effect) = projectile.effect {
+        effects.apply(effect);
+    }
+}
+
+fn spawn_laser_beam(commands: &mut Commands, from: Vec2, to: Vec2) {
+    let midpoint = (from + to) / 2.0;
+    let offset = to - from;
+
+    commands.spawn((
+        Sprite {
+            color: Color::srgba(1.0, 0.1, 0.1, 0.8),
+            custom_size: Some(Vec2::new(offset.length(), 3.0)),
+            ..default()
+        },
+        Transform {
+            translation: Vec3::new(midpoint.x, midpoint.y, 15.0),
+            rotation: Quat::from_rotation_z(offset.y.atan2(offset.x)),
+            ..default()
+        },
+        LaserBeam {
+            timer: Timer::from_seconds(LASER_BEAM_DURATION, TimerMode::Once),
+        },
+    ));
+}
+
+fn fade_laser_beams(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut beams: Query<(Entity, &mut LaserBeam)>,
+) {
+    for (entity, mut beam) in beams.iter_mut() {
+        beam.timer.tick(time.delta());
+        if beam.timer.finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -0,0 +1,272 @@
+use crate::core::game_state::GameState;
+use crate::entities::damage::{deal_damage, DamageType, Resistances};
+use crate::entities::enemy::Enemy;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+pub struct StatusEffectPlugin;
+
+impl Plugin for StatusEffectPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, tick_status_effects.run_if(in_state(GameState::Playing)));
+    }
+}
+
+// Most poison stacks a single enemy can carry
+const MAX_POISON_STACKS: usize = 5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+pub enum StatusEffectKind {
+    Slow,   // strength = fraction of speed removed
+    Poison, // strength = damage per second, stacks
+    Burn,   // strength = damage per second, refreshes
+    Stun,   // no movement while active
+}
+
+/// How a new effect combines with one of the same kind already on the enemy
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StackingRule {
+    Refresh,         // Keep the strongest, reset the duration
+    Stack(usize),    // Add another independent stack, up to a limit
+    LongestDuration, // Keep whichever lasts longer
+}
+
+impl StatusEffectKind {
+    pub fn stacking_rule(&self) -> StackingRule {
+        match self {
+            StatusEffectKind::Slow | StatusEffectKind::Burn => StackingRule::Refresh,
+            StatusEffectKind::Poison => StackingRule::Stack(MAX_POISON_STACKS),
+            StatusEffectKind::Stun => StackingRule::LongestDuration,
+        }
+    }
+
+    /// Damage type dealt over time, if this effect deals damage at all
+    pub fn damage_type(&self) -> Option<DamageType> {
+        match self {
+            StatusEffectKind::Poison | StatusEffectKind::Burn => Some(DamageType::Magic),
+            StatusEffectKind::Slow | StatusEffectKind::Stun => None,
+        }
+    }
+
+    pub fn tint(&self) -> Color {
+        match self {
+            StatusEffectKind::Slow => Color::srgb(0.5, 0.7, 1.0),
+            StatusEffectKind::Poison => Color::srgb(0.5, 1.0, 0.5),
+            StatusEffectKind::Burn => Color::srgb(1.0, 0.6, 0.3),
+            StatusEffectKind::Stun => Color::srgb(1.0, 1.0, 0.4),
+        }
+    }
+
+    // Higher priority tints win when several effects are active
+    fn tint_priority(&self) -> u8 {
+        match self {
+            StatusEffectKind::Slow => 0,
+            StatusEffectKind::Poison => 1,
+            StatusEffectKind::Burn => 2,
+            StatusEffectKind::Stun => 3,
+        }
+    }
+}
+
+/// An effect as defined on a tower or projectile
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct StatusEffect {
+    pub kind: StatusEffectKind,
+    pub duration: f32,
+    #[serde(default)]
+    pub strength: f32,
+}
+
+/// All effects currently on an enemy
+#[derive(Component, Default)]
+pub struct StatusEffects {
+    pub active: Vec<ActiveStatusEffect>,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct ActiveStatusEffect {
+    pub kind: StatusEffectKind,
+    pub strength: f32,
+    pub remaining: f32,
+}
+
+impl StatusEffects {
+    pub fn apply(&mut self, effect: StatusEffect) {
+        let new_effect = ActiveStatusEffect {
+            kind: effect.kind,
+            strength: effect.strength,
+            remaining: effect.duration,
+        };
+
+        match effect.kind.stacking_rule() {
+            StackingRule::Stack(max_stacks) => {
+                let stacks = self.active.iter().filter(|e| e.kind == effect.kind).count();
+                if stacks < max_stacks {
+                    self.active.push(new_effect);
+                } else if let Some(oldest) = self
+                    .active
+                    .iter_mut()
+                    .filter(|e| e.kind == effect.kind)
+                    .min_by(|a, b| a.remaining.total_cmp(&b.remaining))
+                {
+                    *oldest = new_effect;
+                }
+            }
+            StackingRule::Refresh => match self.active.iter_mut().find(|e| e.kind == effect.kind) {
+                Some(existing) => {
+                    existing.strength = existing.strength.max(effect.strength);
+                    existing.remaining = existing.remaining.max(effect.duration);
+                }
+                None => self.active.push(new_effect),
+            },
+            StackingRule::LongestDuration => match self.active.iter_mut().find(|e| e.kind == effect.kind) {
+                Some(existing) => existing.remaining = existing.remaining.max(effect.duration),
+                None => self.active.push(new_effect),
+            },
+        }
+    }
+
+    pub fn has(&self, kind: StatusEffectKind) -> bool {
+        self.active.iter().any(|e| e.kind == kind)
+    }
+
+    /// Multiplier applied to the enemy's movement speed
+    pub fn speed_multiplier(&self) -> f32 {
+        if self.has(StatusEffectKind::Stun) {
+            return 0.0;
+        }
+        self.active
+            .iter()
+            .filter(|e| e.kind == StatusEffectKind::Slow)
+            .fold(1.0, |multiplier, e| multiplier * (1.0 - e.strength.clamp(0.0, 1.0)))
+    }
+
+    pub fn tint(&self) -> Color {
+        self.active
+            .iter()
+            .max_by_key(|e| e.kind.tint_priority())
+            .map(|e| e.kind.tint())
+            .unwrap_or(Color::WHITE)
+    }
+}
+
+fn tick_status_effects(
+    time: Res<Time>,
+    mut enemies: Query<(&mut Enemy, &Resistances, &mut StatusEffects, &mut Sprite)>,
+) {
+    let delta = time.delta_secs();
+
+    for (mut enemy, resistances, mut effects, mut sprite) in enemies.iter_mut() {
+        if effects.active.is_empty() {
+            continue;
+        }
+
+        for effect in effects.active.iter() {
+            if let Some(damage_type) = effect.kind.damage_type() {
+                deal_damage(&mut enemy, resistances, effect.strength * delta, damage_type);
+            }
+        }
+
+        for effect in effects.active.iter_mut() {
+            effect.remaining -= delta;
+        }
+        effects.active.retain(|e| e.remaining > 0.0);
+
+        sprite.color = effects.tint();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn effect(kind: StatusEffectKind, strength: f32, duration: f32) -> StatusEffect {
+        StatusEffect { kind, duration, strength }
+    }
+
+    fn of_kind(effects: &StatusEffects, kind: StatusEffectKind) -> Vec<ActiveStatusEffect> {
+        effects.active.iter().filter(|e| e.kind == kind).copied().collect()
+    }
+
+    #[test]
+    fn refresh_keeps_the_strongest_and_longest() {
+        let mut effects = StatusEffects::default();
+        effects.apply(effect(StatusEffectKind::Slow, 0.3, 2.0));
+        effects.apply(effect(StatusEffectKind::Slow, 0.5, 1.0));
+
+        let slows = of_kind(&effects, StatusEffectKind::Slow);
+        assert_eq!(slows.len(), 1);
+        assert_eq!(slows[0].strength, 0.5);
+        assert_eq!(slows[0].remaining, 2.0);
+    }
+
+    #[test]
+    fn weaker_refresh_only_extends_the_duration() {
+        let mut effects = StatusEffects::default();
+        effects.apply(effect(StatusEffectKind::Burn, 4.0, 1.0));
+        effects.apply(effect(StatusEffectKind::Burn, 2.0, 3.0));
+
+        let burns = of_kind(&effects, StatusEffectKind::Burn);
+        assert_eq!(burns.len(), 1);
+        assert_eq!(burns[0].strength, 4.0);
+        assert_eq!(burns[0].remaining, 3.0);
+    }
+
+    #[test]
+    fn poison_stacks_up_to_the_limit() {
+        let mut effects = StatusEffects::default();
+        for i in 0..MAX_POISON_STACKS {
+            effects.apply(effect(StatusEffectKind::Poison, 1.0, 1.0 + i as f32));
+        }
+        assert_eq!(of_kind(&effects, StatusEffectKind::Poison).len(), MAX_POISON_STACKS);
+
+        // A full stack replaces the one closest to running out
+        effects.apply(effect(StatusEffectKind::Poison, 2.0, 10.0));
+        let poisons = of_kind(&effects, StatusEffectKind::Poison);
+        assert_eq!(poisons.len(), MAX_POISON_STACKS);
+        assert!(poisons.iter().all(|e| e.remaining != 1.0));
+        assert!(poisons.iter().any(|e| e.remaining == 10.0 && e.strength == 2.0));
+    }
+
+    #[test]
+    fn stun_keeps_the_longest_duration() {
+        let mut effects = StatusEffects::default();
+        effects.apply(effect(StatusEffectKind::Stun, 0.0, 2.0));
+        effects.apply(effect(StatusEffectKind::Stun, 0.0, 0.5));
+
+        let stuns = of_kind(&effects, StatusEffectKind::Stun);
+        assert_eq!(stuns.len(), 1);
+        assert_eq!(stuns[0].remaining, 2.0);
+    }
+
+    #[test]
+    fn different_kinds_do_not_interact() {
+        let mut effects = StatusEffects::default();
+        effects.apply(effect(StatusEffectKind::Slow, 0.5, 1.0));
+        effects.apply(effect(StatusEffectKind::Burn, 3.0, 1.0));
+        effects.apply(effect(StatusEffectKind::Poison, 1.0, 1.0));
+
+        assert_eq!(effects.active.len(), 3);
+    }
+
+    #[test]
+    fn speed_multiplier_clamps_slow_strength() {
+        let mut effects = StatusEffects::default();
+        assert_eq!(effects.speed_multiplier(), 1.0);
+
+        effects.apply(effect(StatusEffectKind::Slow, -0.5, 1.0));
+        assert_eq!(effects.speed_multiplier(), 1.0);
+
+        effects.apply(effect(StatusEffectKind::Slow, 2.0, 1.0));
+        assert_eq!(effects.speed_multiplier(), 0.0);
+    }
+
+    #[test]
+    fn stun_stops_movement() {
+        let mut effects = StatusEffects::default();
+        effects.apply(effect(StatusEffectKind::Slow, 0.2, 1.0));
+        effects.apply(effect(StatusEffectKind::Stun, 0.0, 1.0));
+
+        assert_eq!(effects.speed_multiplier(), 0.0);
+    }
+}
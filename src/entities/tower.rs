@@ -1,25 +1,40 @@
-use crate::core::{map::Map, game_state::GameState};
+use crate::core::{map::Map, game_state::{GameState, PlayerResource}};
+use crate::core::utils::{distance, is_point_in_radius, is_pointer_over_ui};
+use crate::entities::animation::{sheet_sprite, AnimationClips, AnimationName, SpriteAnimation, SpriteAtlas};
+use crate::entities::damage::DamageType;
+use crate::entities::enemy::{Enemy, MovementMode};
+use crate::entities::projectile::{spawn_projectile, ProjectileType};
+use crate::entities::status_effect::StatusEffect;
+use crate::levels::level::LevelEntity;
+use bevy::math::Vec3Swizzles;
 use bevy::prelude::*;
 use bevy::window::PrimaryWindow;
+use serde::Deserialize;
 
 pub struct TowerPlugin;
 
 impl Plugin for TowerPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup_towers).add_systems(
-            Update,
-            (
-                tower_targeting,
-                tower_shooting,
-                handle_tower_upgrades,
-                handle_tower_placement,
-            )
-                .run_if(in_state(GameState::Playing)),
-        );
+        app.init_resource::<SelectedTower>()
+            .init_resource::<BuildSelection>()
+            .add_systems(Startup, setup_towers)
+            .add_systems(
+                Update,
+                (
+                    tower_targeting,
+                    tower_shooting,
+                    handle_tower_selection,
+                    handle_tower_upgrades,
+                    handle_tower_placement,
+                    tick_disabled_towers,
+                )
+                    .run_if(in_state(GameState::Playing)),
+            );
     }
 }
 
 #[derive(Component)]
+#[require(LevelEntity)]
 pub struct Tower {
     pub tower_type: TowerType,
     pub range: f32,
@@ -28,43 +43,528 @@ pub struct Tower {
     pub last_shot: f32,
     pub level: u32,
     pub target: Option<Entity>,
+    pub priority: TargetPriority,
+    pub spent: u32, // Total money put into this tower, used for sell refunds
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
 pub enum TowerType {
     Archer,
     Cannon,
     LongBow,
     Splash,
     Slow,
+    AntiAir,
 }
 
-fn setup_towers() {
-    // Initialize tower-related resources
+/// Which enemy movement modes a tower is able to shoot at
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct TargetLayers {
+    pub ground: bool,
+    pub air: bool,
 }
 
-fn tower_targeting() {
-    // Logic for towers to find and select targets
+impl Default for TargetLayers {
+    fn default() -> Self {
+        TargetLayers { ground: true, air: true }
+    }
+}
+
+impl TargetLayers {
+    pub fn can_target(&self, movement: MovementMode) -> bool {
+        match movement {
+            MovementMode::Ground => self.ground,
+            MovementMode::Air => self.air,
+        }
+    }
 }
 
-fn tower_shooting() {
-    // Logic for towers to shoot at targets
+#[derive(Debug, Clone, Copy, Deserialize)]
+pub struct TowerStats {
+    pub range: f32,
+    pub damage: f32,
+    pub fire_rate: f32, // Shots per second
 }
 
-fn handle_tower_upgrades() {
-    // Handle tower upgrades and improvements
+/// Tower definitions loaded from assets/towers.json
+#[derive(Resource, Clone, Default, Deserialize)]
+pub struct TowerCatalog {
+    #[serde(default = "default_sell_refund")]
+    pub sell_refund: f32, // Share of the money spent that selling gives back
+    pub towers: Vec<TowerDefinition>,
 }
 
-fn handle_tower_placement(
+fn default_sell_refund() -> f32 {
+    0.7
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct TowerDefinition {
+    pub tower_type: TowerType,
+    pub cost: u32,
+    pub sprite: String,
+    #[serde(default)]
+    pub atlas: Option<SpriteAtlas>,
+    #[serde(default)]
+    pub animations: AnimationClips, // Needs `atlas`, Idle is played by default
+    #[serde(skip)]
+    pub atlas_layout: Option<Handle<TextureAtlasLayout>>, // Built from `atlas` on load
+    pub projectile_type: ProjectileType,
+    #[serde(default)]
+    pub damage_type: DamageType,
+    #[serde(flatten)]
+    pub stats: TowerStats,       // Level 1 stats
+    #[serde(default)]
+    pub upgrades: Vec<TowerUpgrade>, // Level 2 onwards
+    #[serde(default)]
+    pub branch: Option<TowerBranch>,
+    #[serde(default)]
+    pub effect: Option<StatusEffect>, // Applied to every enemy this tower hits
+    #[serde(default)]
+    pub targets: TargetLayers,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct TowerUpgrade {
+    pub cost: u32,
+    #[serde(flatten)]
+    pub stats: TowerStats,
+}
+
+/// Lets a tower turn into one of several other tower types once it reaches `level`
+#[derive(Clone, Debug, Deserialize)]
+pub struct TowerBranch {
+    pub level: u32,
+    pub cost: u32, // Paid on top of what the tower already cost
+    pub options: Vec<TowerType>,
+}
+
+impl TowerCatalog {
+    pub fn get(&self, tower_type: TowerType) -> Option<&TowerDefinition> {
+        self.towers.iter().find(|tower| tower.tower_type == tower_type)
+    }
+
+    /// Towers sold in the shop; types only reachable through a branch are left out
+    pub fn shop_towers(&self) -> impl Iterator<Item = &TowerDefinition> {
+        self.towers.iter().filter(|tower| {
+            !self
+                .towers
+                .iter()
+                .filter_map(|other| other.branch.as_ref())
+                .any(|branch| branch.options.contains(&tower.tower_type))
+        })
+    }
+}
+
+impl TowerDefinition {
+    pub fn max_level(&self) -> u32 {
+        self.upgrades.len() as u32 + 1
+    }
+
+    /// Cost of going from `level` to the next one, if there is a next one
+    pub fn upgrade_cost(&self, level: u32) -> Option<u32> {
+        self.upgrades
+            .get(level.saturating_sub(1) as usize)
+            .map(|upgrade| upgrade.cost)
+    }
+
+    /// Cost of branching into another tower type, if this tower has branches
+    pub fn branch_cost(&self) -> Option<u32> {
+        self.branch.as_ref().map(|branch| branch.cost)
+    }
+
+    /// Tower types this tower can branch into at the given level
+    pub fn branch_options(&self, level: u32) -> &[TowerType] {
+        match &self.branch {
+            Some(branch) if level >= branch.level => &branch.options,
+            _ => &[],
+        }
+    }
+
+    /// Stats for a tower at the given level, clamped to the levels that exist
+    pub fn stats_for_level(&self, level: u32) -> TowerStats {
+        match level {
+            0 | 1 => self.stats,
+            _ => self
+                .upgrades
+                .get(level as usize - 2)
+                .or(self.upgrades.last())
+                .map(|upgrade| upgrade.stats)
+                .unwrap_or(self.stats),
+        }
+    }
+}
+
+impl Tower {
+    pub fn apply_stats(&mut self, stats: TowerStats) {
+        self.range = stats.range;
+        self.damage = stats.damage;
+        self.fire_rate = stats.fire_rate;
+    }
+
+    pub fn sell_value(&self, catalog: &TowerCatalog) -> u32 {
+        (self.spent as f32 * catalog.sell_refund).round() as u32
+    }
+}
+
+/// Stops a tower from shooting until the timer runs out
+#[derive(Component)]
+pub struct TowerDisabled {
+    pub timer: Timer,
+}
+
+const DISABLED_TOWER_COLOR: Color = Color::srgb(0.4, 0.4, 0.5);
+
+/// Tower shown in the upgrade panel, if any
+#[derive(Resource, Default)]
+pub struct SelectedTower(pub Option<Entity>);
+
+/// Why a tower can't be placed on a tile
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlacementError {
+    NotBuildable,
+    Occupied,
+    NotEnoughMoney,
+}
+
+impl PlacementError {
+    pub fn reason(&self) -> &'static str {
+        match self {
+            PlacementError::NotBuildable => "Can't build here",
+            PlacementError::Occupied => "Tile is occupied",
+            PlacementError::NotEnoughMoney => "Not enough money",
+        }
+    }
+}
+
+/// Checks whether a tower costing `cost` can be placed at `grid_pos`
+pub fn check_placement(map: &Map, grid_pos: UVec2, cost: u32, money: u32) -> Result<(), PlacementError> {
+    if map.is_occupied(grid_pos) {
+        return Err(PlacementError::Occupied);
+    }
+    if !map.is_buildable(grid_pos) {
+        return Err(PlacementError::NotBuildable);
+    }
+    if money < cost {
+        return Err(PlacementError::NotEnoughMoney);
+    }
+    Ok(())
+}
+
+/// Tower type picked in the shop, placed on the next click on a buildable tile
+#[derive(Resource, Default)]
+pub struct BuildSelection(pub Option<TowerType>);
+
+/// Actions offered by the upgrade panel buttons
+#[derive(Component, Debug, Clone, Copy)]
+pub enum TowerAction {
+    Upgrade,
+    Branch(TowerType),
+    CyclePriority,
+    Sell,
+}
+
+/// Which enemy a tower picks when several are in range
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum TargetPriority {
+    #[default]
+    First,     // Closest to the goal
+    Last,      // Furthest from the goal
+    Strongest, // Most health left
+    Weakest,   // Least health left
+    Closest,   // Nearest to the tower
+}
+
+impl TargetPriority {
+    pub fn next(&self) -> Self {
+        match self {
+            TargetPriority::First => TargetPriority::Last,
+            TargetPriority::Last => TargetPriority::Strongest,
+            TargetPriority::Strongest => TargetPriority::Weakest,
+            TargetPriority::Weakest => TargetPriority::Closest,
+            TargetPriority::Closest => TargetPriority::First,
+        }
+    }
+
+    /// Score a candidate target, lower is better
+    fn score(&self, goal_distance: f32, health: f32, tower_distance: f32) -> f32 {
+        match self {
+            TargetPriority::First => goal_distance,
+            TargetPriority::Last => -goal_distance,
+            TargetPriority::Strongest => -health,
+            TargetPriority::Weakest => health,
+            TargetPriority::Closest => tower_distance,
+        }
+    }
+}
+
+fn setup_towers(mut commands: Commands, mut texture_layouts: ResMut<Assets<TextureAtlasLayout>>) {
+    let mut catalog = std::fs::read_to_string("assets/towers.json")
+        .map_err(|e| format!("Error reading tower file: {}", e))
+        .and_then(|json_str| {
+            serde_json::from_str::<TowerCatalog>(&json_str)
+                .map_err(|e| format!("Error parsing JSON: {}", e))
+        })
+        .unwrap_or_else(|e| {
+            error!("Failed to load tower catalog: {}", e);
+            TowerCatalog::default()
+        });
+
+    for definition in catalog.towers.iter_mut() {
+        if let Some(atlas) = &definition.atlas {
+            definition.atlas_layout = Some(texture_layouts.add(atlas.layout()));
+        }
+    }
+
+    info!("Loaded {} tower definitions", catalog.towers.len());
+    commands.insert_resource(catalog);
+}
+
+fn tower_targeting(
+    catalog: Res<TowerCatalog>,
+    mut towers: Query<(&Transform, &mut Tower)>,
+    enemies: Query<(Entity, &Transform, &Enemy)>,
+) {
+    for (tower_transform, mut tower) in towers.iter_mut() {
+        let tower_pos = tower_transform.translation.xy();
+        let targets = catalog
+            .get(tower.tower_type)
+            .map(|definition| definition.targets)
+            .unwrap_or_default();
+
+        // Keep the current target until it dies or leaves range
+        if let Some(target) = tower.target {
+            if let Ok((_, enemy_transform, enemy)) = enemies.get(target)
+                && enemy.health > 0.0
+                && targets.can_target(enemy.movement)
+                && is_point_in_radius(tower_pos, enemy_transform.translation.xy(), tower.range)
+            {
+                continue;
+            }
+            tower.target = None;
+        }
+
+        let mut best: Option<(Entity, f32)> = None;
+
+        for (entity, enemy_transform, enemy) in enemies.iter() {
+            let enemy_pos = enemy_transform.translation.xy();
+            if enemy.health <= 0.0
+                || !targets.can_target(enemy.movement)
+                || !is_point_in_radius(tower_pos, enemy_pos, tower.range)
+            {
+                continue;
+            }
+
+            let score = tower
+                .priority
+                .score(enemy.distance_to_goal, enemy.health, distance(tower_pos, enemy_pos));
+
+            if best.is_none_or(|(_, best_score)| score < best_score) {
+                best = Some((entity, score));
+            }
+        }
+
+        tower.target = best.map(|(entity, _)| entity);
+    }
+}
+
+fn tower_shooting(
     mut commands: Commands,
+    time: Res<Time>,
+    catalog: Res<TowerCatalog>,
+    mut towers: Query<(&Transform, &mut Tower, Option<&mut SpriteAnimation>), Without<TowerDisabled>>,
+    enemies: Query<&Transform, With<Enemy>>,
+) {
+    let now = time.elapsed_secs();
+
+    for (tower_transform, mut tower, animation) in towers.iter_mut() {
+        let Some(target) = tower.target else { continue; };
+        let Ok(enemy_transform) = enemies.get(target) else { continue; };
+
+        // fire_rate is shots per second
+        if tower.fire_rate <= 0.0 || now - tower.last_shot < 1.0 / tower.fire_rate {
+            continue;
+        }
+
+        let Some(definition) = catalog.get(tower.tower_type) else { continue; };
+
+        spawn_projectile(
+            &mut commands,
+            tower_transform.translation.xy(),
+            target,
+            enemy_transform.translation.xy(),
+            tower.damage, // Includes upgrades, unlike the definition
+            definition,
+        );
+        tower.last_shot = now;
+        if let Some(mut animation) = animation {
+            animation.play(AnimationName::Attack);
+        }
+    }
+}
+
+fn tower_animation(definition: &TowerDefinition) -> Option<SpriteAnimation> {
+    definition.atlas_layout.as_ref()?;
+    SpriteAnimation::new(&definition.animations, AnimationName::Idle)
+}
+
+fn tick_disabled_towers(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut towers: Query<(Entity, &mut TowerDisabled, &mut Sprite)>,
+) {
+    for (entity, mut disabled, mut sprite) in towers.iter_mut() {
+        disabled.timer.tick(time.delta());
+        if disabled.timer.finished() {
+            commands.entity(entity).remove::<TowerDisabled>();
+            sprite.color = Color::WHITE;
+        } else if sprite.color != DISABLED_TOWER_COLOR {
+            sprite.color = DISABLED_TOWER_COLOR;
+        }
+    }
+}
+
+fn handle_tower_selection(
     mouse_input: Res<ButtonInput<MouseButton>>,
     windows: Query<&Window, With<PrimaryWindow>>,
     camera_q: Query<(&Camera, &GlobalTransform)>,
+    ui_interactions: Query<&Interaction>,
     map: Res<Map>,
+    mut selected: ResMut<SelectedTower>,
+) {
+    if !mouse_input.just_pressed(MouseButton::Left) || is_pointer_over_ui(&ui_interactions) {
+        return;
+    }
+
+    let (camera, camera_transform) = camera_q.single();
+    let window = windows.single();
+
+    let Some(cursor_position) = window.cursor_position() else { return; };
+    let Ok(world_position) = camera.viewport_to_world_2d(camera_transform, cursor_position) else {
+        return;
+    };
+
+    let clicked = map.tower_at(map.world_to_grid(world_position));
+
+    if selected.0 != clicked {
+        selected.0 = clicked;
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_tower_upgrades(
+    mut commands: Commands,
+    buttons: Query<(&Interaction, &TowerAction), Changed<Interaction>>,
+    mut selected: ResMut<SelectedTower>,
+    mut towers: Query<(&Transform, &mut Tower, &mut Sprite)>,
+    catalog: Res<TowerCatalog>,
+    mut map: ResMut<Map>,
     asset_server: Res<AssetServer>,
+    mut player_resources: ResMut<PlayerResource>,
+) {
+    let Some(tower_entity) = selected.0 else { return; };
+
+    for (interaction, action) in buttons.iter() {
+        if *interaction != Interaction::Pressed {
+            continue;
+        }
+
+        let Ok((transform, mut tower, mut sprite)) = towers.get_mut(tower_entity) else {
+            selected.0 = None;
+            return;
+        };
+        let Some(definition) = catalog.get(tower.tower_type) else { continue; };
+
+        match *action {
+            TowerAction::Upgrade => {
+                let Some(cost) = definition.upgrade_cost(tower.level) else {
+                    info!("{:?} is already at max level", tower.tower_type);
+                    continue;
+                };
+                if player_resources.money < cost {
+                    info!("Not enough money to upgrade tower");
+                    continue;
+                }
+                player_resources.money -= cost;
+                tower.spent += cost;
+                tower.level += 1;
+                let stats = definition.stats_for_level(tower.level);
+                tower.apply_stats(stats);
+                info!("{:?} upgraded to level {}", tower.tower_type, tower.level);
+            }
+            TowerAction::Branch(tower_type) => {
+                if !definition.branch_options(tower.level).contains(&tower_type) {
+                    continue;
+                }
+                let Some(branch_definition) = catalog.get(tower_type) else {
+                    warn!("No tower definition for {:?}", tower_type);
+                    continue;
+                };
+                let cost = definition.branch_cost().unwrap_or_default();
+                if player_resources.money < cost {
+                    info!("Not enough money to upgrade tower");
+                    continue;
+                }
+                player_resources.money -= cost;
+                tower.spent += cost;
+                tower.tower_type = tower_type;
+                // Upgrades already bought carry over into the new tower type
+                tower.level = tower.level.min(branch_definition.max_level());
+                let stats = branch_definition.stats_for_level(tower.level);
+                tower.apply_stats(stats);
+                *sprite = sheet_sprite(
+                    asset_server.load(&branch_definition.sprite),
+                    branch_definition.atlas.as_ref(),
+                    branch_definition.atlas_layout.clone(),
+                );
+                match tower_animation(branch_definition) {
+                    Some(animation) => commands.entity(tower_entity).insert(animation),
+                    None => commands.entity(tower_entity).remove::<SpriteAnimation>(),
+                };
+                info!("Tower branched into {:?}", tower_type);
+            }
+            TowerAction::CyclePriority => {
+                tower.priority = tower.priority.next();
+                tower.target = None;
+            }
+            TowerAction::Sell => {
+                let refund = tower.sell_value(&catalog);
+                player_resources.money += refund;
+
+                let grid_pos = map
+                    .tile_of(tower_entity)
+                    .unwrap_or_else(|| map.world_to_grid(transform.translation.xy()));
+                map.vacate(grid_pos);
+
+                commands.entity(tower_entity).despawn_recursive();
+                selected.0 = None;
+                info!("Tower sold for {} at grid position: {:?}", refund, grid_pos);
+                return;
+            }
+        }
+
+        // Refresh the panel with the new stats
+        selected.set_changed();
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn handle_tower_placement(
+    mut commands: Commands,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_q: Query<(&Camera, &GlobalTransform)>,
+    ui_interactions: Query<&Interaction>,
+    mut map: ResMut<Map>,
+    asset_server: Res<AssetServer>,
+    catalog: Res<TowerCatalog>,
+    build_selection: Res<BuildSelection>,
     player_resources: Option<ResMut<crate::core::game_state::PlayerResource>>,
 ) {
-    if mouse_input.just_pressed(MouseButton::Left) {
+    let Some(tower_type) = build_selection.0 else { return; };
+
+    if mouse_input.just_pressed(MouseButton::Left) && !is_pointer_over_ui(&ui_interactions) {
         let (camera, camera_transform) = camera_q.single();
         let window = windows.single();
 
@@ -74,38 +574,52 @@ fn handle_tower_placement(
             {
                 let grid_pos = map.world_to_grid(world_position);
 
-                if map.is_buildable(grid_pos) {
-                    let tower_cost = 0;
-
-                    if let Some(mut resources) = player_resources {
-                        if resources.money < tower_cost {   
-                            info!("Not enough money to build tower");
-                            return;
-                        }
-                        resources.money -= tower_cost;
-                    };
-
-                    let world_pos = map.grid_to_world(grid_pos);
-
-                    commands.spawn((
-                        Sprite {
-                            image: asset_server.load("textures/archer01.png"),
-                            ..default()
-                        },
-                        Transform::from_translation(Vec3::new(world_pos.x, world_pos.y, 10.0)),
-                        Tower {
-                            tower_type: TowerType::Archer,
-                            range: 150.0,
-                            damage: 10.0,
-                            fire_rate: 1.0,
-                            last_shot: 0.0,
-                            level: 1,
-                            target: None,
-                        },
-                    ));
-
-                    info!("Tower placed at grid position: {:?}", grid_pos);
+                let Some(definition) = catalog.get(tower_type) else {
+                    warn!("No tower definition for {:?}", tower_type);
+                    return;
+                };
+                let tower_cost = definition.cost;
+                let money = player_resources.as_ref().map_or(u32::MAX, |resources| resources.money);
+
+                if let Err(error) = check_placement(&map, grid_pos, tower_cost, money) {
+                    info!("Can't place tower at {:?}: {}", grid_pos, error.reason());
+                    return;
                 }
+
+                if let Some(mut resources) = player_resources {
+                    resources.money -= tower_cost;
+                };
+
+                let world_pos = map.grid_to_world(grid_pos);
+
+                let tower_entity = commands.spawn((
+                    sheet_sprite(
+                        asset_server.load(&definition.sprite),
+                        definition.atlas.as_ref(),
+                        definition.atlas_layout.clone(),
+                    ),
+                    Transform::from_translation(Vec3::new(world_pos.x, world_pos.y, 10.0)),
+                    Tower {
+                        tower_type,
+                        range: definition.stats.range,
+                        damage: definition.stats.damage,
+                        fire_rate: definition.stats.fire_rate,
+                        last_shot: 0.0,
+                        level: 1,
+                        target: None,
+                        priority: TargetPriority::default(),
+                        spent: tower_cost,
+                    },
+                )).id();
+
+                if let Some(animation) = tower_animation(definition) {
+                    commands.entity(tower_entity).insert(animation);
+                }
+
+                // The tile is taken until the tower is sold
+                map.occupy(grid_pos, tower_entity);
+
+                info!("Tower placed at grid position: {:?}", grid_pos);
             }
         }
     }
@@ -33,6 +33,8 @@ pub enum ContextMenuOption {
     PathTool,
     StartPoint,
     EndPoint,
+    SpawnPoint,
+    ExitPoint,
     BuildableArea,
     Delete,
     Save,
@@ -1,7 +1,6 @@
 use bevy::prelude::*;
 
 pub mod components;
-pub mod data;
 pub mod resources;
 pub mod state;
 pub mod systems;
@@ -1,6 +1,8 @@
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
+use crate::levels::level::WaveData;
+
 #[derive(Resource)]
 pub struct EditorTextInput {
     pub level_name: String,
@@ -22,16 +24,25 @@ pub enum EditorTool {
     PathPlacer,
     StartPoint,
     EndPoint,
+    SpawnPoint, // Toggles an extra spawn portal
+    ExitPoint,  // Toggles an extra exit
     BuildableArea,
     TextureSelector, 
 }
 
+// Marker colours for the extra portals, set apart from the start and end markers
+pub const SPAWN_MARKER_COLOR: Color = Color::srgba(0.6, 0.9, 0.2, 0.6);
+pub const EXIT_MARKER_COLOR: Color = Color::srgba(0.9, 0.5, 0.1, 0.6);
+
 #[derive(Resource, Default)]
 pub struct EditorData {
     pub path: Vec<(UVec2, String)>, 
     pub start: Option<UVec2>,
     pub end: Option<UVec2>,
+    pub spawns: Vec<UVec2>, // Spawn portals after `start`
+    pub exits: Vec<UVec2>,  // Exits after `end`
     pub buildable_areas: Vec<UVec2>,
+    pub waves: Vec<WaveData>, // Not editable here, kept so saving doesn't lose them
     pub current_tool: EditorTool,
     pub grid_overlay: bool,
 }
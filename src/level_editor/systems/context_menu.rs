@@ -100,6 +100,8 @@ pub fn spawn_context_menu(
                     spawn_menu_option(parent, "Path Tool", ContextMenuOption::PathTool);
                     spawn_menu_option(parent, "Start Point", ContextMenuOption::StartPoint);
                     spawn_menu_option(parent, "End Point", ContextMenuOption::EndPoint);
+                    spawn_menu_option(parent, "Spawn Portal", ContextMenuOption::SpawnPoint);
+                    spawn_menu_option(parent, "Exit", ContextMenuOption::ExitPoint);
                     spawn_menu_option(parent, "Buildable Area", ContextMenuOption::BuildableArea);
 
                     parent.spawn((
@@ -181,6 +183,12 @@ pub fn handle_context_menu_interaction(
                 ContextMenuOption::EndPoint => {
                     editor_data.current_tool = EditorTool::EndPoint;
                 }
+                ContextMenuOption::SpawnPoint => {
+                    editor_data.current_tool = EditorTool::SpawnPoint;
+                }
+                ContextMenuOption::ExitPoint => {
+                    editor_data.current_tool = EditorTool::ExitPoint;
+                }
                 ContextMenuOption::BuildableArea => {
                     editor_data.current_tool = EditorTool::BuildableArea;
                 }
@@ -211,6 +219,13 @@ pub fn handle_context_menu_interaction(
                                 deleted_something = true;
                             }
 
+                            let original_portal_len = editor_data.spawns.len() + editor_data.exits.len();
+                            editor_data.spawns.retain(|&pos| pos != grid_pos);
+                            editor_data.exits.retain(|&pos| pos != grid_pos);
+                            if editor_data.spawns.len() + editor_data.exits.len() < original_portal_len {
+                                deleted_something = true;
+                            }
+
                             for (entity, transform) in markers_query.iter() {
                                 if transform.translation.truncate().distance(world_pos) < 1.0 {
                                     commands.entity(entity).despawn();
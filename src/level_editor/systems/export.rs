@@ -3,9 +3,9 @@ use std::io::Write;
 use bevy::log::{error, info, warn};
 use bevy::prelude::UVec2;
 
+use crate::levels::level::LevelData;
 use crate::levels::level_textures::PathTexture;
 use crate::level_editor::resources::EditorData;
-use super::super::data::LevelData;
 
 pub fn export_level(editor_data: &EditorData, level_name: &str) {
     if level_name.is_empty() {
@@ -38,12 +38,27 @@ pub fn export_level(editor_data: &EditorData, level_name: &str) {
 
     let start_point = editor_data.start.unwrap_or(UVec2::ZERO);
     let end_point = editor_data.end.unwrap_or(UVec2::ZERO);
+    let dir_path = "assets/levels";
+    let file_path = format!("{}/{}.json", dir_path, sanitized_name);
+
+    // The editor can't author waves, so keep the ones already in the file being replaced
+    let waves = if editor_data.waves.is_empty() {
+        std::fs::read_to_string(&file_path)
+            .ok()
+            .and_then(|json_str| serde_json::from_str::<LevelData>(&json_str).ok())
+            .map(|existing| existing.waves)
+            .unwrap_or_default()
+    } else {
+        editor_data.waves.clone()
+    };
 
     let level_data = LevelData {
         path: path_coords,
         path_textures,
         start: vec![start_point.x, start_point.y],
         end: vec![end_point.x, end_point.y],
+        spawns: editor_data.spawns.iter().map(|pos| vec![pos.x, pos.y]).collect(),
+        exits: editor_data.exits.iter().map(|pos| vec![pos.x, pos.y]).collect(),
         buildable_areas: editor_data
             .buildable_areas
             .iter()
@@ -54,17 +69,16 @@ pub fn export_level(editor_data: &EditorData, level_name: &str) {
             crate::core::config::GRID_WIDTH as u32,
             crate::core::config::GRID_HEIGHT as u32
         ],
+        waves,
     };
 
     match serde_json::to_string_pretty(&level_data) {
         Ok(json_string) => {
-            let dir_path = "assets/levels";
             if let Err(e) = std::fs::create_dir_all(dir_path) {
                 error!("Failed to create levels directory '{}': {}", dir_path, e);
                 return;
             }
 
-            let file_path = format!("{}/{}.json", dir_path, sanitized_name);
             match File::create(&file_path) {
                 Ok(mut file) => {
                     if let Err(e) = file.write_all(json_string.as_bytes()) {
@@ -136,6 +136,44 @@ pub fn editor_input_handler(
                             EditorPathMarker, 
                         ));
                     }
+                    EditorTool::SpawnPoint | EditorTool::ExitPoint => {
+                        let is_spawn = editor_data.current_tool == EditorTool::SpawnPoint;
+                        let portals = if is_spawn {
+                            &mut editor_data.spawns
+                        } else {
+                            &mut editor_data.exits
+                        };
+
+                        // Clicking an existing portal removes it again
+                        if let Some(idx) = portals.iter().position(|&pos| pos == grid_pos) {
+                            portals.remove(idx);
+                            // Portal markers sit above the path tile on the same spot
+                            for (entity, transform, _) in markers_query.iter() {
+                                if transform.translation.truncate().distance(world_pos) < 1.0
+                                    && transform.translation.z == 1.5
+                                {
+                                    commands.entity(entity).despawn();
+                                    break;
+                                }
+                            }
+                        } else {
+                            portals.push(grid_pos);
+                            let color = if is_spawn {
+                                SPAWN_MARKER_COLOR
+                            } else {
+                                EXIT_MARKER_COLOR
+                            };
+                            commands.spawn((
+                                Sprite {
+                                    color,
+                                    custom_size: Some(Vec2::splat(CELL_SIZE)),
+                                    ..default()
+                                },
+                                Transform::from_translation(world_pos.extend(1.5)),
+                                EditorPathMarker,
+                            ));
+                        }
+                    }
                     EditorTool::BuildableArea => {
                         if !editor_data.buildable_areas.contains(&grid_pos) {
                             editor_data.buildable_areas.push(grid_pos);
@@ -178,6 +216,10 @@ pub fn editor_input_handler(
         new_tool = Some(EditorTool::StartPoint);
     } else if key_press.just_pressed(KeyCode::KeyE) {
         new_tool = Some(EditorTool::EndPoint);
+    } else if key_press.just_pressed(KeyCode::KeyN) {
+        new_tool = Some(EditorTool::SpawnPoint);
+    } else if key_press.just_pressed(KeyCode::KeyX) {
+        new_tool = Some(EditorTool::ExitPoint);
     } else if key_press.just_pressed(KeyCode::KeyB) {
         new_tool = Some(EditorTool::BuildableArea);
     } else if key_press.just_pressed(KeyCode::KeyT) { 
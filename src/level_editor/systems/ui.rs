@@ -101,6 +101,44 @@ pub fn setup_editor_ui(mut commands: Commands) {
                     ));
                 });
 
+            // Spawn Portal Button
+            parent
+                .spawn((
+                    Button,
+                    button_style.clone(),
+                    BackgroundColor(button_color),
+                    EditorButton(EditorTool::SpawnPoint),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("Spawn (N)"),
+                        TextFont {
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor::WHITE,
+                    ));
+                });
+
+            // Exit Button
+            parent
+                .spawn((
+                    Button,
+                    button_style.clone(),
+                    BackgroundColor(button_color),
+                    EditorButton(EditorTool::ExitPoint),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new("Exit (X)"),
+                        TextFont {
+                            font_size: 16.0,
+                            ..default()
+                        },
+                        TextColor::WHITE,
+                    ));
+                });
+
             // Buildable Area Button
             parent
                 .spawn((
@@ -0,0 +1,170 @@
+use crate::core::game_state::{GameMode, GameState, PlayerResource};
+use crate::core::map::Map;
+use crate::entities::enemy::{EnemyCatalog, EnemyDefinition};
+use crate::levels::level::{EnemyGroup, Level, Wave};
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const BEST_SCORES_FILE: &str = "best_scores.json";
+
+pub struct EndlessPlugin;
+
+impl Plugin for EndlessPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Startup, load_best_scores)
+            .add_systems(OnEnter(GameState::GameOver), record_endless_score)
+            // Quitting a run from the pause menu ends it too
+            .add_systems(
+                OnTransition {
+                    exited: GameState::Paused,
+                    entered: GameState::MainMenu,
+                },
+                record_endless_score,
+            );
+    }
+}
+
+// Difficulty budget of the first wave, spent using enemy rewards as their cost
+const BASE_BUDGET: f32 = 40.0;
+// Budget multiplier per wave
+const BUDGET_GROWTH: f32 = 1.12;
+// Extra enemy health per wave, as a fraction of the archetype's health
+const HEALTH_GROWTH: f32 = 0.08;
+// Number of different enemy types mixed into one wave
+const TYPES_PER_WAVE: usize = 3;
+// Every n-th wave also brings a boss
+const BOSS_WAVE_INTERVAL: u32 = 10;
+const WAVE_DELAY: f32 = 8.0;
+
+/// Generates waves forever once a level runs out of its own
+#[derive(Clone, Copy, Debug)]
+pub struct EndlessWaves {
+    pub seed: u64,
+}
+
+impl EndlessWaves {
+    pub fn new(seed: u64) -> Self {
+        EndlessWaves { seed }
+    }
+
+    /// Builds wave `wave_number` (starting at 1); the same seed always gives the same wave
+    pub fn generate(&self, wave_number: u32, catalog: &EnemyCatalog, spawn_count: usize) -> Wave {
+        let mut rng =
+            StdRng::seed_from_u64(self.seed ^ (wave_number as u64).wrapping_mul(0x9E37_79B9_7F4A_7C15));
+        let step = wave_number.saturating_sub(1);
+        let mut budget = BASE_BUDGET * BUDGET_GROWTH.powi(step as i32);
+
+        let spawn_count = spawn_count.max(1);
+        let mut groups = Vec::new();
+
+        if wave_number.is_multiple_of(BOSS_WAVE_INTERVAL)
+            && let Some(boss) = catalog.enemies.iter().find(|enemy| !enemy.phases.is_empty())
+        {
+            groups.push(EnemyGroup {
+                enemy_type: boss.enemy_type.clone(),
+                count: 1,
+                spawn: rng.random_range(0..spawn_count),
+            });
+            budget -= enemy_cost(boss);
+        }
+
+        // Pick a few regular archetypes to mix into this wave
+        let mut pool: Vec<&EnemyDefinition> =
+            catalog.enemies.iter().filter(|enemy| enemy.phases.is_empty()).collect();
+        let mut picked = Vec::new();
+        while picked.len() < TYPES_PER_WAVE && !pool.is_empty() {
+            picked.push(pool.swap_remove(rng.random_range(0..pool.len())));
+        }
+
+        // Spend what's left of the budget on random picks until nothing fits
+        let mut counts = vec![0u32; picked.len()];
+        loop {
+            let affordable: Vec<usize> = (0..picked.len())
+                .filter(|&i| enemy_cost(picked[i]) <= budget)
+                .collect();
+            if affordable.is_empty() {
+                break;
+            }
+            let choice = affordable[rng.random_range(0..affordable.len())];
+            counts[choice] += 1;
+            budget -= enemy_cost(picked[choice]);
+        }
+
+        // Each group comes out of a random spawn portal
+        for (enemy, count) in picked.iter().zip(counts) {
+            if count > 0 {
+                groups.push(EnemyGroup {
+                    enemy_type: enemy.enemy_type.clone(),
+                    count,
+                    spawn: rng.random_range(0..spawn_count),
+                });
+            }
+        }
+
+        Wave {
+            groups,
+            spawn_interval: (1.0 - 0.03 * step as f32).max(0.25),
+            wave_delay: WAVE_DELAY,
+            health_multiplier: 1.0 + HEALTH_GROWTH * step as f32,
+        }
+    }
+}
+
+fn enemy_cost(enemy: &EnemyDefinition) -> f32 {
+    enemy.reward.max(1) as f32
+}
+
+/// Appends a generated wave whenever an endless level is about to run out
+pub fn extend_endless_waves(mut level: ResMut<Level>, catalog: Res<EnemyCatalog>, map: Res<Map>) {
+    let Some(endless) = level.endless else { return; };
+    if level.has_next_wave() {
+        return;
+    }
+    let wave_number = level.waves.len() as u32 + 1;
+    let wave = endless.generate(wave_number, &catalog, map.starts.len());
+    level.waves.push(wave);
+}
+
+/// Highest endless score reached on each level, saved between runs
+#[derive(Resource, Default, Serialize, Deserialize)]
+pub struct BestScores {
+    pub endless: HashMap<u32, u32>,
+}
+
+impl BestScores {
+    pub fn endless_best(&self, level: u32) -> u32 {
+        self.endless.get(&level).copied().unwrap_or(0)
+    }
+}
+
+fn load_best_scores(mut commands: Commands) {
+    let best_scores = std::fs::read_to_string(BEST_SCORES_FILE)
+        .ok()
+        .and_then(|json_str| serde_json::from_str::<BestScores>(&json_str).ok())
+        .unwrap_or_default();
+    commands.insert_resource(best_scores);
+}
+
+fn record_endless_score(
+    game_mode: Res<GameMode>,
+    level: Option<Res<Level>>,
+    player_resources: Res<PlayerResource>,
+    mut best_scores: ResMut<BestScores>,
+) {
+    let (GameMode::Endless, Some(level)) = (*game_mode, level) else { return; };
+    if player_resources.score <= best_scores.endless_best(level.current_level) {
+        return;
+    }
+
+    info!("New endless best on level {}: {}", level.current_level, player_resources.score);
+    best_scores.endless.insert(level.current_level, player_resources.score);
+    let saved = serde_json::to_string_pretty(&*best_scores)
+        .map_err(|e| e.to_string())
+        .and_then(|json_str| std::fs::write(BEST_SCORES_FILE, json_str).map_err(|e| e.to_string()));
+    if let Err(e) = saved {
+        error!("Failed to save best scores: {}", e);
+    }
+}
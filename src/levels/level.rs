@@ -1,9 +1,11 @@
 use crate::core::config::{CELL_SIZE, GRID_HEIGHT, GRID_WIDTH};
 use crate::core::map::Map;
-use crate::core::game_state::GameState;
-use crate::entities::enemy::{EnemyType, spawn_enemy};
+use crate::core::game_state::{GameMode, GameState, PlayerResource};
+use crate::entities::enemy::{spawn_enemy_at, EnemyCatalog, EnemyType, LeakStats, SpawnModifiers};
 use crate::entities::pathfinding::{FlowDirection, FlowField};
+use crate::levels::endless::{extend_endless_waves, EndlessWaves};
 use crate::levels::level_textures::PathTexture;
+use rand::Rng;
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 
@@ -13,28 +15,232 @@ impl Plugin for LevelPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Startup, setup_level.run_if(not(in_state(GameState::Editor))))
             // app.add_systems(Startup, setup_level)
-            .add_systems(Update, spawn_wave_system.run_if(in_state(GameState::Playing)))
-            // .add_systems(Update, check_wave_progress)
-            .add_event::<WaveCompleteEvent>();
+            .add_systems(
+                OnTransition {
+                    exited: GameState::MainMenu,
+                    entered: GameState::Playing,
+                },
+                restart_level,
+            )
+            .add_systems(
+                Update,
+                (
+                    // A freshly loaded level is in place before its waves run
+                    handle_load_level,
+                    (extend_endless_waves, send_next_wave, run_waves, check_wave_progress)
+                        .chain()
+                        .run_if(in_state(GameState::Playing).and(resource_exists::<Level>)),
+                )
+                    .chain(),
+            )
+            .add_event::<WaveCompleteEvent>()
+            .add_event::<LevelCompleteEvent>()
+            .add_event::<SendNextWaveEvent>()
+            .add_event::<LoadLevelEvent>();
     }
 }
 
+// Level files in play order, with the number each level is known by
+const LEVEL_FILES: &[(u32, &str)] = &[
+    (3, "assets/levels/level_03.json"),
+    (4, "assets/levels/level_4.json"),
+    (5, "assets/levels/level_05.json"),
+];
+
+/// The level that follows `level`, if there is one
+pub fn next_level(level: u32) -> Option<u32> {
+    let index = LEVEL_FILES.iter().position(|(number, _)| *number == level)?;
+    LEVEL_FILES.get(index + 1).map(|(number, _)| *number)
+}
+
 #[derive(Resource)]
 pub struct Level {
     pub current_level: u32,
     pub waves: Vec<Wave>,
-    pub current_wave_index: usize,
-    pub wave_in_progress: bool,
+    pub current_wave_index: usize,       // Next wave to start
+    pub next_wave_timer: Option<Timer>,  // Counts down once the field is clear
+    pub active_waves: Vec<ActiveWave>,   // Waves still spawning or with enemies alive
+    pub enemies_remaining: usize,
+    pub completed: bool, // Every wave has been cleared
+    pub endless: Option<EndlessWaves>, // Keeps adding waves after the level's own
+}
+
+/// A wave that has started and isn't cleared yet
+pub struct ActiveWave {
+    pub wave_index: usize,
     pub spawn_timer: Timer,
-    pub enemies_to_spawn: Vec<EnemyType>,
+    pub enemies_to_spawn: Vec<(EnemyType, usize)>, // (type, spawn portal)
     pub enemies_spawned: usize,
-    pub enemies_remaining: usize,
 }
 
+impl ActiveWave {
+    pub fn finished_spawning(&self) -> bool {
+        self.enemies_spawned >= self.enemies_to_spawn.len()
+    }
+}
+
+/// Marks which wave an enemy was spawned by
+#[derive(Component)]
+pub struct WaveMember(pub usize);
+
+// Money paid per second of wave delay skipped by calling a wave early
+const EARLY_WAVE_BONUS_PER_SECOND: f32 = 2.0;
+
+impl Level {
+    pub fn new(current_level: u32, waves: Vec<Wave>) -> Self {
+        // The first wave waits for its own delay, giving the player time to build
+        let next_wave_timer = waves
+            .first()
+            .map(|wave| Timer::from_seconds(wave.wave_delay, TimerMode::Once));
+        Level {
+            current_level,
+            waves,
+            current_wave_index: 0,
+            next_wave_timer,
+            active_waves: Vec::new(),
+            enemies_remaining: 0,
+            completed: false,
+            endless: None,
+        }
+    }
+
+    pub fn has_next_wave(&self) -> bool {
+        self.current_wave_index < self.waves.len()
+    }
+
+    pub fn wave_in_progress(&self) -> bool {
+        !self.active_waves.is_empty()
+    }
+
+    /// Bonus for starting the next wave now instead of waiting for its delay
+    pub fn early_call_bonus(&self) -> u32 {
+        // Only a running countdown can be cut short, stacking waves on top of each other pays nothing
+        let Some(timer) = self.next_wave_timer.as_ref().filter(|_| self.has_next_wave()) else {
+            return 0;
+        };
+        (timer.remaining_secs() * EARLY_WAVE_BONUS_PER_SECOND).round() as u32
+    }
+
+    /// Queues up every enemy of the next wave and starts spawning them
+    fn start_next_wave(&mut self) {
+        let Some(wave) = self.waves.get(self.current_wave_index) else { return; };
+
+        let mut enemies_to_spawn = Vec::new();
+        for group in &wave.groups {
+            for _ in 0..group.count {
+                enemies_to_spawn.push((group.enemy_type.clone(), group.spawn));
+            }
+        }
+
+        info!("Wave {} started", self.current_wave_index + 1);
+        let spawn_timer = Timer::from_seconds(wave.spawn_interval, TimerMode::Repeating);
+        self.active_waves.push(ActiveWave {
+            wave_index: self.current_wave_index,
+            spawn_timer,
+            enemies_to_spawn,
+            enemies_spawned: 0,
+        });
+        self.current_wave_index += 1;
+        self.next_wave_timer = None;
+    }
+}
+
+/// Asks for the next wave to start right away
+#[derive(Event)]
+pub struct SendNextWaveEvent;
+
 pub struct Wave {
-    pub enemy_types: Vec<(EnemyType, u32)>, // (type, count)
-    pub spawn_interval: f32,                // Time between enemy spawns
-    pub wave_delay: f32,                    // Delay before wave starts
+    pub groups: Vec<EnemyGroup>,
+    pub spawn_interval: f32,    // Time between enemy spawns
+    pub wave_delay: f32,        // Delay before wave starts
+    pub health_multiplier: f32, // Applied to every enemy in the wave
+}
+
+/// Enemies of one type coming out of one spawn portal
+pub struct EnemyGroup {
+    pub enemy_type: EnemyType,
+    pub count: u32,
+    pub spawn: usize, // Index into the map's spawn portals
+}
+
+impl EnemyGroup {
+    /// A group coming out of the first spawn portal
+    pub fn new(enemy_type: EnemyType, count: u32) -> Self {
+        EnemyGroup {
+            enemy_type,
+            count,
+            spawn: 0,
+        }
+    }
+}
+
+/// A wave as stored in the level file
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WaveData {
+    pub enemies: Vec<EnemyGroupData>,
+    pub spawn_interval: f32,
+    pub wave_delay: f32,
+    #[serde(default)]
+    pub spawn: SpawnRef, // Spawn portal for groups that don't pick their own
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct EnemyGroupData {
+    pub enemy_type: EnemyType,
+    pub count: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub spawn: Option<SpawnRef>,
+}
+
+/// Where a wave or group comes from, either a portal index or the portal's `[x, y]` tile
+///
+/// Portal 0 is the level's `start`, 1 and up are its `spawns`.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SpawnRef {
+    Portal(usize),
+    Tile(Vec<u32>),
+}
+
+impl Default for SpawnRef {
+    fn default() -> Self {
+        SpawnRef::Portal(0)
+    }
+}
+
+impl SpawnRef {
+    /// Index of the portal this refers to among `portals`
+    pub fn portal(&self, portals: &[UVec2]) -> usize {
+        match self {
+            SpawnRef::Portal(index) => *index,
+            SpawnRef::Tile(coords) => {
+                let tile = UVec2::new(coords[0], coords[1]);
+                portals.iter().position(|&portal| portal == tile).unwrap_or_else(|| {
+                    warn!("Wave spawn {:?} isn't a spawn portal, using the start", tile);
+                    0
+                })
+            }
+        }
+    }
+}
+
+impl WaveData {
+    pub fn to_wave(&self, portals: &[UVec2]) -> Wave {
+        Wave {
+            groups: self
+                .enemies
+                .iter()
+                .map(|group| EnemyGroup {
+                    enemy_type: group.enemy_type.clone(),
+                    count: group.count,
+                    spawn: group.spawn.as_ref().unwrap_or(&self.spawn).portal(portals),
+                })
+                .collect(),
+            spawn_interval: self.spawn_interval,
+            wave_delay: self.wave_delay,
+            health_multiplier: 1.0,
+        }
+    }
 }
 
 #[derive(Event)]
@@ -42,14 +248,37 @@ pub struct WaveCompleteEvent {
     pub wave_index: usize,
 }
 
-#[derive(Resource, Clone, Deserialize)]
+/// Sent once the last wave of a level has been cleared
+#[derive(Event)]
+pub struct LevelCompleteEvent {
+    pub level: u32,
+}
+
+/// Replaces the current map, towers and enemies with a fresh copy of `level`
+#[derive(Event)]
+pub struct LoadLevelEvent {
+    pub level: u32,
+}
+
+/// Belongs to the level being played and is despawned when a level is loaded
+#[derive(Component, Default)]
+pub struct LevelEntity;
+
+/// A level file, read by the game and written by the level editor
+#[derive(Resource, Clone, Serialize, Deserialize)]
 pub struct LevelData {
     pub path: Vec<Vec<u32>>, // Stored as [[x, y], [x, y], ...]
     pub path_textures: Vec<PathTexture>,
-    pub start: Vec<u32>,                // [x, y]
+    pub start: Vec<u32>,                // [x, y], spawn portal 0
     pub end: Vec<u32>,                  // [x, y]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub spawns: Vec<Vec<u32>>, // Extra spawn portals, numbered from 1
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exits: Vec<Vec<u32>>,  // Extra exits
     pub buildable_areas: Vec<Vec<u32>>, // [[x, y], [x, y], ...]
     pub dimensions: Vec<u32>,           // [width, height]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub waves: Vec<WaveData>, // Falls back to create_waves when empty
 }
 
 fn create_map() -> Map {
@@ -83,14 +312,13 @@ fn create_map() -> Map {
         }
     }
 
-    Map {
-        grid_size: Vec2::new(CELL_SIZE, CELL_SIZE),
-        dimensions: UVec2::new(GRID_WIDTH as u32, GRID_HEIGHT as u32),
+    Map::from_tiles(
+        UVec2::new(GRID_WIDTH as u32, GRID_HEIGHT as u32),
         path_tiles,
         buildable_tiles,
-        start: UVec2::new(0, 10), // Start at the beginning of the path
-        end: UVec2::new(26, 8),  // End at the end of the path
-    }
+        vec![UVec2::new(0, 10)], // Start at the beginning of the path
+        vec![UVec2::new(26, 8)], // End at the end of the path
+    )
 }
 
 // Helper to determine if a position is near the path (within buffer distance)
@@ -118,36 +346,44 @@ fn create_waves() -> Vec<Wave> {
     vec![
         // Wave 1: Basic enemies
         Wave {
-            enemy_types: vec![(EnemyType::Basic, 10)],
+            groups: vec![EnemyGroup::new(EnemyType::Basic, 10)],
             spawn_interval: 1.0,
             wave_delay: 3.0,
+            health_multiplier: 1.0,
         },
         // Wave 2: Basic and Fast enemies
         Wave {
-            enemy_types: vec![(EnemyType::Basic, 8), (EnemyType::Fast, 5)],
+            groups: vec![
+                EnemyGroup::new(EnemyType::Basic, 8),
+                EnemyGroup::new(EnemyType::Fast, 5),
+            ],
             spawn_interval: 0.8,
             wave_delay: 5.0,
+            health_multiplier: 1.0,
         },
         // Wave 3: Basic, Fast, and Tank enemies
         Wave {
-            enemy_types: vec![
-                (EnemyType::Basic, 10),
-                (EnemyType::Fast, 8),
-                (EnemyType::Tank, 3),
+            groups: vec![
+                EnemyGroup::new(EnemyType::Basic, 10),
+                EnemyGroup::new(EnemyType::Fast, 8),
+                EnemyGroup::new(EnemyType::Tank, 3),
             ],
             spawn_interval: 0.7,
             wave_delay: 7.0,
+            health_multiplier: 1.0,
         },
-        // Wave 4: All enemy types including Boss
+        // Wave 4: All enemy types including flyers and the Boss
         Wave {
-            enemy_types: vec![
-                (EnemyType::Basic, 15),
-                (EnemyType::Fast, 10),
-                (EnemyType::Tank, 5),
-                (EnemyType::Boss, 1),
+            groups: vec![
+                EnemyGroup::new(EnemyType::Basic, 15),
+                EnemyGroup::new(EnemyType::Fast, 10),
+                EnemyGroup::new(EnemyType::Tank, 5),
+                EnemyGroup::new(EnemyType::from("Flyer".to_string()), 6),
+                EnemyGroup::new(EnemyType::Boss, 1),
             ],
             spawn_interval: 0.5,
             wave_delay: 10.0,
+            health_multiplier: 1.0,
         },
     ]
 }
@@ -171,6 +407,7 @@ fn spawn_map_visuals_with_textures(
                     ..default()
                 },
                 Transform::from_translation(Vec3::new(world_pos.x, world_pos.y, 0.0)),
+                LevelEntity,
             ));
         }
     }
@@ -208,63 +445,78 @@ fn spawn_map_visuals_with_textures(
                 ..default()
             },
             Transform::from_translation(Vec3::new(world_pos.x, world_pos.y, 0.1)),
+            LevelEntity,
         ));
     }
 
-    // Add visual indicators for start and end points
-    let start_pos = map.grid_to_world(map.start);
-    let end_pos = map.grid_to_world(map.end);
-
-    // Start portal
-    commands.spawn((
-        Sprite {
-            image: asset_server.load("textures/start_portal.png"),
-            custom_size: Some(Vec2::new(CELL_SIZE, CELL_SIZE)),
-            ..default()
-        },
-        Transform::from_translation(Vec3::new(start_pos.x, start_pos.y, 0.2)),
-    ));
-
-    // End portal
-    commands.spawn((
-        Sprite {
-            image: asset_server.load("textures/end_portal.png"),
-            custom_size: Some(Vec2::new(CELL_SIZE, CELL_SIZE)),
-            ..default()
-        },
-        Transform::from_translation(Vec3::new(end_pos.x, end_pos.y, 0.2)),
-    ));
+    // Add visual indicators for every spawn portal and exit
+    let portals = map
+        .starts
+        .iter()
+        .map(|&start| (start, "textures/start_portal.png"))
+        .chain(map.ends.iter().map(|&end| (end, "textures/end_portal.png")));
+    for (grid_pos, texture) in portals {
+        let portal_pos = map.grid_to_world(grid_pos);
+        commands.spawn((
+            Sprite {
+                image: asset_server.load(texture),
+                custom_size: Some(Vec2::new(CELL_SIZE, CELL_SIZE)),
+                ..default()
+            },
+            Transform::from_translation(Vec3::new(portal_pos.x, portal_pos.y, 0.2)),
+            LevelEntity,
+        ));
+    }
 }
 
-// Update the setup_level function in level.rs
 fn setup_level(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let level_data_result = std::fs::read_to_string("assets/levels/level_03.json")
+    load_level(&mut commands, &asset_server, LEVEL_FILES[0].0, None);
+}
+
+fn load_level(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    level_number: u32,
+    endless: Option<EndlessWaves>,
+) {
+    let level_file = LEVEL_FILES
+        .iter()
+        .find(|(number, _)| *number == level_number)
+        .map(|(_, path)| *path)
+        .unwrap_or(LEVEL_FILES[0].1);
+    let level_data_result = std::fs::read_to_string(level_file)
         .map_err(|e| format!("Error reading level file: {}", e))
         .and_then(|json_str| {
             serde_json::from_str::<LevelData>(&json_str)
                 .map_err(|e| format!("Error parsing JSON: {}", e))
         });
 
-    let map = if let Ok(level_data) = &level_data_result {
-        Map {
-            grid_size: Vec2::new(CELL_SIZE, CELL_SIZE),
-            dimensions: UVec2::new(level_data.dimensions[0], level_data.dimensions[1]),
-            path_tiles: level_data
+    let map = match &level_data_result {
+        Ok(level_data) => Map::from_tiles(
+            UVec2::new(level_data.dimensions[0], level_data.dimensions[1]),
+            level_data
                 .path
                 .iter()
                 .map(|coords| UVec2::new(coords[0], coords[1]))
                 .collect(),
-            buildable_tiles: level_data
+            level_data
                 .buildable_areas
                 .iter()
                 .map(|coords| UVec2::new(coords[0], coords[1]))
                 .collect(),
-            start: UVec2::new(level_data.start[0], level_data.start[1]),
-            end: UVec2::new(level_data.end[0], level_data.end[1]),
+            std::iter::once(&level_data.start)
+                .chain(&level_data.spawns)
+                .map(|coords| UVec2::new(coords[0], coords[1]))
+                .collect(),
+            std::iter::once(&level_data.end)
+                .chain(&level_data.exits)
+                .map(|coords| UVec2::new(coords[0], coords[1]))
+                .collect(),
+        ),
+        Err(e) => {
+            error!("Failed to load {}: {}, using fallback map", level_file, e);
+            create_map()
         }
-    } else {
-        info!("Failed to load level data, using fallback map");
-        create_map()
     };
 
     // info!("buildable tiles: {:?}", map.buildable_tiles);
@@ -273,113 +525,182 @@ fn setup_level(mut commands: Commands, asset_server: Res<AssetServer>) {
     let map_width = map.dimensions.x as usize;
     let map_height = map.dimensions.y as usize;
     
-    let goal_x = map.end.x.min(map_width as u32 - 1);
-    let goal_y = map.end.y.min(map_height as u32 - 1);
-    let goal_pos = UVec2::new(goal_x, goal_y);
+    let goals: Vec<UVec2> = map
+        .ends
+        .iter()
+        .map(|end| end.min(UVec2::new(map_width as u32 - 1, map_height as u32 - 1)))
+        .collect();
     
     let mut flow_field = FlowField::new(map_width, map_height);
-    flow_field.compute(&map, goal_pos);
+    flow_field.compute(&map, &goals);
     
-    info!("Flow field initialized with goal at: {:?}", goal_pos);
-    info!("Start position is: {:?}", map.start);
+    info!("Flow field initialized with goals at: {:?}", goals);
+    info!("Spawn portals are: {:?}", map.starts);
     info!("Map has {} path tiles", map.path_tiles.len());
 
     commands.insert_resource(flow_field);
+    let waves = match &level_data_result {
+        Ok(level_data) if !level_data.waves.is_empty() => {
+            level_data.waves.iter().map(|wave| wave.to_wave(&map.starts)).collect()
+        }
+        _ => {
+            info!("Level has no waves, using the default set");
+            create_waves()
+        }
+    };
+    let mut level = Level::new(level_number, waves);
+    level.endless = endless;
+    commands.insert_resource(level);
 
-    spawn_map_visuals_with_textures(&mut commands, &asset_server, &map, level_data_result.ok());
+    spawn_map_visuals_with_textures(commands, asset_server, &map, level_data_result.ok());
 
     commands.insert_resource(map);
 }
 
-fn spawn_wave_system(
+// Counts down to the next wave, then spawns the enemies of every active wave
+fn run_waves(
     mut commands: Commands,
+    time: Res<Time>,
     asset_server: Res<AssetServer>,
     map: Res<Map>,
     flow_field: Option<Res<FlowField>>,
-    time: Res<Time>,
-    enemies: Query<&crate::entities::enemy::Enemy>,
+    catalog: Res<EnemyCatalog>,
+    mut level: ResMut<Level>,
 ) {
-    if enemies.is_empty() && flow_field.is_some() && flow_field.as_ref().unwrap().is_initialized {
-        let start_pos = map.grid_to_world(map.start);
-        
-        commands.spawn((
-            Sprite {
-                image: asset_server.load("textures/enemies/enemy.png"),
-                custom_size: Some(Vec2::new(32.0, 32.0)),
+    if level.completed || !flow_field.is_some_and(|field| field.is_initialized) {
+        return;
+    }
+
+    if let Some(timer) = level.next_wave_timer.as_mut() {
+        timer.tick(time.delta());
+        if timer.finished() {
+            level.start_next_wave();
+        }
+    }
+
+    let level = &mut *level;
+    for active_wave in level.active_waves.iter_mut() {
+        // The first enemy comes out as soon as the wave starts, the rest one interval apart
+        let first = active_wave.enemies_spawned == 0;
+        active_wave.spawn_timer.tick(time.delta());
+        if !(first || active_wave.spawn_timer.just_finished()) || active_wave.finished_spawning() {
+            continue;
+        }
+        if first {
+            active_wave.spawn_timer.reset();
+        }
+
+        let (enemy_type, spawn) = active_wave.enemies_to_spawn[active_wave.enemies_spawned].clone();
+        let spawned = spawn_enemy_at(
+            &mut commands,
+            &asset_server,
+            &catalog,
+            enemy_type,
+            map.grid_to_world(map.spawn_point(spawn)),
+            SpawnModifiers {
+                health: level.waves[active_wave.wave_index].health_multiplier,
                 ..default()
             },
-            Transform::from_translation(Vec3::new(start_pos.x, start_pos.y, 10.0)),
-            crate::entities::enemy::Enemy {
-                health: 100.0,
-                speed: 200.0,
-                reward: 10,
-                enemy_type: EnemyType::Basic,
-                path_index: 0,
-                path_progress: 0.0,
-            },
-        ));
-        
-        info!("Spawned a basic enemy at: {:?}", start_pos);
+        );
+        if let Some(entity) = spawned {
+            commands.entity(entity).insert(WaveMember(active_wave.wave_index));
+        }
+        active_wave.enemies_spawned += 1;
     }
 }
 
+fn send_next_wave(
+    mut send_events: EventReader<SendNextWaveEvent>,
+    mut level: ResMut<Level>,
+    mut player_resources: ResMut<PlayerResource>,
+) {
+    for _ in send_events.read() {
+        if level.completed || !level.has_next_wave() {
+            continue;
+        }
+        let bonus = level.early_call_bonus();
+        player_resources.money += bonus;
+        info!("Wave {} called early for {} bonus", level.current_wave_index + 1, bonus);
+        level.start_next_wave();
+    }
+}
 
 fn check_wave_progress(
     mut level: ResMut<Level>,
-    enemies: Query<&crate::entities::enemy::Enemy>,
+    enemies: Query<Option<&WaveMember>, With<crate::entities::enemy::Enemy>>,
     mut wave_complete_events: EventWriter<WaveCompleteEvent>,
-    game_state: Option<State<crate::core::game_state::GameState>>,
+    mut level_complete_events: EventWriter<LevelCompleteEvent>,
 ) {
-    // Skip if game isn't in Playing state
-    if let Some(state) = game_state {
-        if *state != crate::core::game_state::GameState::Playing {
-            return;
-        }
+    if level.completed {
+        return;
     }
+    level.enemies_remaining = enemies.iter().count();
 
-    // If wave is in progress and all enemies are spawned
-    if level.wave_in_progress && level.enemies_spawned >= level.enemies_to_spawn.len() {
-        // Count remaining enemies
-        let remaining = enemies.iter().count();
-        level.enemies_remaining = remaining;
-
-        // If no enemies remain, wave is complete
-        if remaining == 0 {
-            info!("Wave {} completed!", level.current_wave_index + 1);
-
-            // Send wave complete event
-            wave_complete_events.send(WaveCompleteEvent {
-                wave_index: level.current_wave_index,
-            });
-
-            // Set up for next wave
-            level.current_wave_index += 1;
-            level.wave_in_progress = false;
-
-            // If there are more waves, prepare the next one
-            if level.current_wave_index < level.waves.len() {
-                let next_wave = &level.waves[level.current_wave_index];
-
-                // Prepare enemies for next wave
-                let mut enemies_to_spawn = Vec::new();
-                for (enemy_type, count) in &next_wave.enemy_types {
-                    for _ in 0..*count {
-                        enemies_to_spawn.push(*enemy_type);
-                    }
-                }
-
-                // Set up timer for wave delay
-                // level.spawn_timer = Timer::from_seconds(next_wave.wave_delay, TimerMode::Once);
-                // level.enemies_to_spawn = enemies_to_spawn;
-                // level.enemies_spawned = 0;
-                // level.enemies_remaining = enemies_to_spawn.len();
-
-                info!("Next wave starting in {} seconds", next_wave.wave_delay);
-            } else {
-                // All waves completed
-                info!("All waves completed! Level finished!");
-                // You could trigger a level complete event here
-            }
+    // A wave is cleared once it has spawned everything and none of its enemies are left
+    let mut cleared = Vec::new();
+    level.active_waves.retain(|active_wave| {
+        let alive = enemies
+            .iter()
+            .any(|member| member.is_some_and(|member| member.0 == active_wave.wave_index));
+        if active_wave.finished_spawning() && !alive {
+            cleared.push(active_wave.wave_index);
+            false
+        } else {
+            true
         }
+    });
+
+    for wave_index in cleared {
+        info!("Wave {} completed!", wave_index + 1);
+        wave_complete_events.send(WaveCompleteEvent { wave_index });
+    }
+
+    if level.wave_in_progress() || level.next_wave_timer.is_some() {
+        return;
+    }
+
+    // If there are more waves, count down to the next one
+    if let Some(next_wave) = level.waves.get(level.current_wave_index) {
+        let wave_delay = next_wave.wave_delay;
+        level.next_wave_timer = Some(Timer::from_seconds(wave_delay, TimerMode::Once));
+        info!("Next wave starting in {} seconds", wave_delay);
+    } else if level.enemies_remaining == 0 {
+        // All waves completed, including anything they summoned
+        info!("All waves completed! Level finished!");
+        level.completed = true;
+        level_complete_events.send(LevelCompleteEvent {
+            level: level.current_level,
+        });
+    }
+}
+
+// Clears out everything from the previous run and builds the requested level from its file
+fn handle_load_level(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    game_mode: Res<GameMode>,
+    mut load_events: EventReader<LoadLevelEvent>,
+    mut leak_stats: ResMut<LeakStats>,
+    mut player_resource: ResMut<PlayerResource>,
+    leftovers: Query<Entity, With<LevelEntity>>,
+) {
+    let Some(event) = load_events.read().last() else { return; };
+
+    for entity in leftovers.iter() {
+        commands.entity(entity).despawn_recursive();
     }
+    *leak_stats = LeakStats::default();
+    player_resource.reset();
+
+    let endless = (*game_mode == GameMode::Endless).then(|| EndlessWaves::new(rand::rng().random()));
+    info!("Loading level {}", event.level);
+    // The new Map starts without any tower occupancy
+    load_level(&mut commands, &asset_server, event.level, endless);
+}
+
+// Starting from the main menu replays the current level from scratch
+fn restart_level(level: Option<Res<Level>>, mut load_events: EventWriter<LoadLevelEvent>) {
+    load_events.send(LoadLevelEvent {
+        level: level.map_or(LEVEL_FILES[0].0, |level| level.current_level),
+    });
 }
@@ -3,7 +3,9 @@ use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::PathBuf;
 
-use crate::levels::level_editor::{EditorData, LevelData, EditorPathMarker};
+use crate::level_editor::components::EditorPathMarker;
+use crate::level_editor::resources::{EditorData, EXIT_MARKER_COLOR, SPAWN_MARKER_COLOR};
+use crate::levels::level::LevelData;
 
 // Function to read level files from the assets/levels directory
 pub fn get_level_files() -> Vec<String> {
@@ -57,7 +59,10 @@ pub fn apply_level_data(
     editor_data.path.clear();
     editor_data.start = None;
     editor_data.end = None;
+    editor_data.spawns.clear();
+    editor_data.exits.clear();
     editor_data.buildable_areas.clear();
+    editor_data.waves.clear();
 
      // Despawn existing markers
      for (entity, _) in markers_query.iter() {
@@ -90,6 +95,18 @@ pub fn apply_level_data(
         .map(|area| UVec2::new(area[0], area[1]))
         .collect();
 
+    editor_data.spawns = level_data
+        .spawns
+        .iter()
+        .map(|spawn| UVec2::new(spawn[0], spawn[1]))
+        .collect();
+    editor_data.exits = level_data
+        .exits
+        .iter()
+        .map(|exit| UVec2::new(exit[0], exit[1]))
+        .collect();
+    editor_data.waves = level_data.waves.clone();
+
     // Spawn new markers
     let grid_size = Vec2::new(48.0, 48.0); // Assuming grid size is 48x48
 
@@ -144,6 +161,28 @@ pub fn apply_level_data(
         ));
     }
 
+    let portals = editor_data
+        .spawns
+        .iter()
+        .map(|&pos| (pos, SPAWN_MARKER_COLOR))
+        .chain(editor_data.exits.iter().map(|&pos| (pos, EXIT_MARKER_COLOR)));
+    for (portal_pos, color) in portals {
+        let world_pos = Vec2::new(
+            portal_pos.x as f32 * grid_size.x + grid_size.x * 0.5,
+            portal_pos.y as f32 * grid_size.y + grid_size.y * 0.5,
+        );
+
+        commands.spawn((
+            Sprite {
+                color,
+                custom_size: Some(Vec2::new(grid_size.x, grid_size.y)),
+                ..default()
+            },
+            Transform::from_translation(world_pos.extend(1.5)),
+            EditorPathMarker,
+        ));
+    }
+
     for buildable_area in &editor_data.buildable_areas {
         let world_pos = Vec2::new(
             buildable_area.x as f32 * grid_size.x + grid_size.x * 0.5,
@@ -6,10 +6,15 @@ pub mod core {
 }
 
 pub mod entities {
+    pub mod ability;
+    pub mod animation;
+    pub mod boss;
+    pub mod damage;
     pub mod enemy;
     pub mod projectile;
     pub mod tower;
     pub mod pathfinding;
+    pub mod status_effect;
 }
 
 pub mod ui {
@@ -17,9 +22,13 @@ pub mod ui {
     pub mod render;
     pub mod ui_components;
     pub mod overlay;
+    pub mod tower_panel;
+    pub mod shop;
+    pub mod wave_panel;
 }
 
 pub mod levels {
+    pub mod endless;
     pub mod level;
     // pub mod level_editor;
     pub mod level_loader;
@@ -2,10 +2,10 @@ use bevy::prelude::*;
 
 use towerdefense::{
     core::{config::{WINDOW_HEIGHT, WINDOW_WIDTH}, game_state::GameStatePlugin},
-    entities::{enemy::EnemyPlugin, projectile::ProjectilePlugin, tower::TowerPlugin, pathfinding::PathfindingPlugin},
-    levels::{level::LevelPlugin, level_textures::TexturesPlugin},
+    entities::{ability::AbilityPlugin, animation::AnimationPlugin, boss::BossPlugin, enemy::EnemyPlugin, projectile::ProjectilePlugin, tower::TowerPlugin, pathfinding::PathfindingPlugin, status_effect::StatusEffectPlugin},
+    levels::{endless::EndlessPlugin, level::LevelPlugin, level_textures::TexturesPlugin},
     level_editor::EditorPlugin,
-    ui::{main_menu::MainMenuPlugin, render::RenderPlugin, overlay::OverlayPlugin},
+    ui::{main_menu::MainMenuPlugin, render::RenderPlugin, overlay::OverlayPlugin, tower_panel::TowerPanelPlugin, shop::ShopPlugin, wave_panel::WavePanelPlugin},
 };
 
 fn main() {
@@ -27,10 +27,18 @@ fn main() {
         .add_plugins(EnemyPlugin)
         .add_plugins(PathfindingPlugin)
         .add_plugins(LevelPlugin)
+        .add_plugins(EndlessPlugin)
         .add_plugins(TowerPlugin)
         .add_plugins(ProjectilePlugin)
+        .add_plugins(StatusEffectPlugin)
+        .add_plugins(AbilityPlugin)
+        .add_plugins(BossPlugin)
+        .add_plugins(AnimationPlugin)
         .add_plugins(TexturesPlugin)
         .add_plugins(OverlayPlugin)
+        .add_plugins(TowerPanelPlugin)
+        .add_plugins(ShopPlugin)
+        .add_plugins(WavePanelPlugin)
         .run();
 }
 
@@ -1,5 +1,7 @@
 use bevy::prelude::*;
-use crate::core::game_state::GameState;
+use crate::core::game_state::{GameMode, GameState};
+use crate::levels::endless::BestScores;
+use crate::levels::level::{next_level, Level, LoadLevelEvent};
 
 pub struct MainMenuPlugin;
 
@@ -8,21 +10,26 @@ impl Plugin for MainMenuPlugin {
         app.add_systems(OnEnter(GameState::MainMenu), setup_main_menu)
            .add_systems(OnEnter(GameState::GameOver), setup_game_over)
            .add_systems(OnEnter(GameState::Paused), setup_pause_menu)
+           .add_systems(OnEnter(GameState::Victory), setup_victory)
            .add_systems(Update, button_interactions.run_if(in_state(GameState::MainMenu)))
            .add_systems(Update, pause_menu_interactions.run_if(in_state(GameState::Paused)))
            .add_systems(Update, game_over_interactions.run_if(in_state(GameState::GameOver)))
+           .add_systems(Update, victory_interactions.run_if(in_state(GameState::Victory)))
            .add_systems(OnExit(GameState::MainMenu), cleanup_menu_ui)
            .add_systems(OnExit(GameState::Paused), cleanup_menu_ui)
-           .add_systems(OnExit(GameState::GameOver), cleanup_menu_ui);
+           .add_systems(OnExit(GameState::GameOver), cleanup_menu_ui)
+           .add_systems(OnExit(GameState::Victory), cleanup_menu_ui);
     }
 }
 
 #[derive(Component)]
 enum MenuButton {
     Play,
+    Endless,
     Quit,
     MainMenu,
     Resume,
+    NextLevel(u32),
 }
 
 #[derive(Component)]
@@ -68,6 +75,7 @@ fn setup_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
                 })
                 .with_children(|parent| {
                     spawn_button(parent, "Play Game", MenuButton::Play);
+                    spawn_button(parent, "Endless", MenuButton::Endless);
                     spawn_button(parent, "Quit", MenuButton::Quit);
                 });
         });
@@ -123,7 +131,20 @@ fn setup_pause_menu(mut commands: Commands) {
 fn setup_game_over(
     mut commands: Commands,
     player_resource: Res<crate::core::game_state::PlayerResource>,
+    game_mode: Res<GameMode>,
+    level: Option<Res<Level>>,
+    best_scores: Option<Res<BestScores>>,
 ) {
+    // The new score may not be recorded yet, so count it here too
+    let endless_best = match (*game_mode, level, best_scores) {
+        (GameMode::Endless, Some(level), Some(best_scores)) => Some(
+            best_scores
+                .endless_best(level.current_level)
+                .max(player_resource.score),
+        ),
+        _ => None,
+    };
+
     commands
         .spawn((
             Node {
@@ -166,6 +187,17 @@ fn setup_game_over(
                 },
             ));
 
+            if let Some(best) = endless_best {
+                parent.spawn((
+                    Text::new(format!("Endless best: {}", best)),
+                    TextFont { 
+                        font_size: 24.0,
+                        ..default()
+                    },
+                    TextColor(Color::srgb(1.0, 0.85, 0.3)),
+                ));
+            }
+
             parent
                 .spawn(Node {
                     margin: UiRect::all(Val::Px(20.0)),
@@ -177,6 +209,100 @@ fn setup_game_over(
         });
 }
 
+fn setup_victory(
+    mut commands: Commands,
+    player_resource: Res<crate::core::game_state::PlayerResource>,
+    level: Res<Level>,
+) {
+    let stars = player_resource.star_rating();
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                align_items: AlignItems::Center,
+                justify_content: JustifyContent::Center,
+                flex_direction: FlexDirection::Column,
+                padding: UiRect::all(Val::Px(20.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.05, 0.1, 0.1)),
+            MenuUI,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(format!("Level {} Complete", level.current_level)),
+                TextFont { 
+                    font_size: 60.0,
+                    ..default()
+                },
+                TextColor(Color::srgb(0.3, 1.0, 0.4)),
+                Node {
+                    margin: UiRect::all(Val::Px(20.0)),
+                    ..default()
+                },
+            ));
+
+            // Star rating, lit stars first
+            parent
+                .spawn(Node {
+                    column_gap: Val::Px(15.0),
+                    margin: UiRect::all(Val::Px(10.0)),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    for star in 0..3 {
+                        let color = if star < stars {
+                            Color::srgb(1.0, 0.85, 0.2)
+                        } else {
+                            Color::srgb(0.25, 0.25, 0.25)
+                        };
+                        parent.spawn((
+                            Node {
+                                width: Val::Px(40.0),
+                                height: Val::Px(40.0),
+                                ..default()
+                            },
+                            BackgroundColor(color),
+                        ));
+                    }
+                });
+
+            parent.spawn((
+                Text::new(format!(
+                    "Health left: {}   Score: {}",
+                    player_resource.health, player_resource.score
+                )),
+                TextFont { 
+                    font_size: 30.0,
+                    ..default()
+                },
+                TextColor::WHITE,
+                Node {
+                    margin: UiRect::all(Val::Px(20.0)),
+                    ..default()
+                },
+            ));
+
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    margin: UiRect::all(Val::Px(20.0)),
+                    row_gap: Val::Px(20.0),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    if let Some(next) = next_level(level.current_level) {
+                        spawn_button(parent, "Next Level", MenuButton::NextLevel(next));
+                    }
+                    spawn_button(parent, "Main Menu", MenuButton::MainMenu);
+                });
+        });
+}
+
 fn spawn_button(parent: &mut ChildBuilder, text: &str, button_type: MenuButton) {
     parent
         .spawn((
@@ -207,6 +333,7 @@ fn spawn_button(parent: &mut ChildBuilder, text: &str, button_type: MenuButton)
 fn button_interactions(
     mut buttons: Query<(&Interaction, &MenuButton, &mut BackgroundColor), Changed<Interaction>>,
     mut next_state: ResMut<NextState<GameState>>,
+    mut game_mode: ResMut<GameMode>,
     mut exit: EventWriter<bevy::app::AppExit>,
 ) {
     for (interaction, button_type, mut color) in buttons.iter_mut() {
@@ -214,6 +341,11 @@ fn button_interactions(
             Interaction::Pressed => {
                 match button_type {
                     MenuButton::Play => {
+                        *game_mode = GameMode::Campaign;
+                        next_state.set(GameState::Playing);
+                    }
+                    MenuButton::Endless => {
+                        *game_mode = GameMode::Endless;
                         next_state.set(GameState::Playing);
                     }
                     MenuButton::Quit => {
@@ -268,6 +400,33 @@ fn game_over_interactions(
     }
 }
 
+fn victory_interactions(
+    mut buttons: Query<(&Interaction, &MenuButton, &mut BackgroundColor), Changed<Interaction>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut load_events: EventWriter<LoadLevelEvent>,
+) {
+    for (interaction, button_type, mut color) in buttons.iter_mut() {
+        match *interaction {
+            Interaction::Pressed => match button_type {
+                MenuButton::NextLevel(level) => {
+                    load_events.send(LoadLevelEvent { level: *level });
+                    next_state.set(GameState::Playing);
+                }
+                MenuButton::MainMenu => {
+                    next_state.set(GameState::MainMenu);
+                }
+                _ => {}
+            },
+            Interaction::Hovered => {
+                *color = BackgroundColor(Color::srgb(0.25, 0.25, 0.25));
+            }
+            Interaction::None => {
+                *color = BackgroundColor(Color::srgb(0.15, 0.15, 0.15));
+            }
+        }
+    }
+}
+
 fn cleanup_menu_ui(mut commands: Commands, query: Query<Entity, With<MenuUI>>) {
     for entity in query.iter() {
         commands.entity(entity).despawn_recursive();
@@ -1,15 +1,22 @@
 use bevy::prelude::*;
+use bevy::sprite::Anchor;
 use bevy::window::PrimaryWindow;
 
-use crate::core::game_state::PlayerResource;
+use crate::core::game_state::{PlayerResource, STARTING_HEALTH};
+use crate::entities::boss::Boss;
+use crate::entities::enemy::{Enemy, EnemyLeakedEvent};
+use crate::levels::level::LevelEntity;
 
 pub struct OverlayPlugin;
 
 impl Plugin for OverlayPlugin {
     fn build(&self, app: &mut App) {
         app.init_resource::<MyWorldCoords>()
+            .init_resource::<LeakFlash>()
             // app.add_systems(Startup, setup)
-            .add_systems(Update, health_bar);
+            .add_systems(Update, (health_bar, flash_health_bar_on_leak).chain())
+            .add_systems(Update, (spawn_enemy_health_bars, update_enemy_health_bars).chain())
+            .add_systems(Update, update_boss_health_bar);
         // .add_systems(Update, my_cursor_system);
     }
 }
@@ -30,7 +37,7 @@ fn health_bar(
 
     let player_health = player_resources.map(|pr| pr.health).unwrap_or(0);
     // info!("player health: {}", player_health);
-    let player_max_health = 100;
+    let player_max_health = STARTING_HEALTH;
 
     let health_index = if player_max_health > 0 {
         ((1.0 - (player_health as f32 / player_max_health as f32)) * 10.0).round() as usize
@@ -63,6 +70,221 @@ fn health_bar(
     }
 }
 
+const LEAK_FLASH_DURATION: f32 = 0.4;
+const LEAK_FLASH_COLOR: Color = Color::srgb(1.0, 0.2, 0.2);
+
+/// Counts down while the player health bar flashes after a leak
+#[derive(Resource)]
+struct LeakFlash(Timer);
+
+impl Default for LeakFlash {
+    fn default() -> Self {
+        let mut timer = Timer::from_seconds(LEAK_FLASH_DURATION, TimerMode::Once);
+        timer.tick(timer.duration());
+        LeakFlash(timer)
+    }
+}
+
+fn flash_health_bar_on_leak(
+    time: Res<Time>,
+    mut leak_events: EventReader<EnemyLeakedEvent>,
+    mut flash: ResMut<LeakFlash>,
+    mut query: Query<&mut Sprite, With<HealthBar>>,
+) {
+    if leak_events.read().count() > 0 {
+        flash.0.reset();
+    }
+    flash.0.tick(time.delta());
+
+    let Ok(mut sprite) = query.get_single_mut() else { return; };
+    // Fades from the flash colour back to white
+    let color = LEAK_FLASH_COLOR.mix(&Color::WHITE, flash.0.fraction());
+    if sprite.color != color {
+        sprite.color = color;
+    }
+}
+
+const ENEMY_BAR_WIDTH: f32 = 32.0;
+const ENEMY_BAR_HEIGHT: f32 = 4.0;
+// Distance above the enemy's centre, scaled with the enemy sprite
+const ENEMY_BAR_OFFSET: f32 = 28.0;
+
+/// Floating health bar that follows an enemy around
+#[derive(Component)]
+pub struct EnemyHealthBar {
+    pub owner: Entity,
+}
+
+#[derive(Component)]
+struct EnemyHealthBarFill;
+
+type EnemyHealthBarFillFilter = (With<EnemyHealthBarFill>, Without<EnemyHealthBar>, Without<Enemy>);
+
+// Bars are separate entities rather than children so they don't rotate with the enemy
+fn spawn_enemy_health_bars(mut commands: Commands, enemies: Query<Entity, Added<Enemy>>) {
+    for owner in enemies.iter() {
+        commands
+            .spawn((
+                Sprite {
+                    color: Color::srgba(0.0, 0.0, 0.0, 0.7),
+                    custom_size: Some(Vec2::new(ENEMY_BAR_WIDTH, ENEMY_BAR_HEIGHT)),
+                    ..default()
+                },
+                Transform::from_translation(Vec3::new(0.0, 0.0, 12.0)),
+                Visibility::Hidden,
+                EnemyHealthBar { owner },
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Sprite {
+                        color: Color::srgb(0.2, 0.9, 0.2),
+                        custom_size: Some(Vec2::new(ENEMY_BAR_WIDTH, ENEMY_BAR_HEIGHT)),
+                        anchor: Anchor::CenterLeft,
+                        ..default()
+                    },
+                    Transform::from_translation(Vec3::new(-ENEMY_BAR_WIDTH / 2.0, 0.0, 0.1)),
+                    EnemyHealthBarFill,
+                ));
+            });
+    }
+}
+
+fn update_enemy_health_bars(
+    mut commands: Commands,
+    enemies: Query<(&Transform, &Enemy)>,
+    mut bars: Query<
+        (Entity, &EnemyHealthBar, &mut Transform, &mut Visibility, &Children),
+        Without<Enemy>,
+    >,
+    mut fills: Query<(&mut Transform, &mut Sprite), EnemyHealthBarFillFilter>,
+) {
+    for (entity, bar, mut transform, mut visibility, children) in bars.iter_mut() {
+        let Ok((enemy_transform, enemy)) = enemies.get(bar.owner) else {
+            commands.entity(entity).despawn_recursive();
+            continue;
+        };
+
+        transform.translation.x = enemy_transform.translation.x;
+        transform.translation.y =
+            enemy_transform.translation.y + ENEMY_BAR_OFFSET * enemy_transform.scale.y;
+
+        let ratio = if enemy.max_health > 0.0 {
+            (enemy.health / enemy.max_health).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+
+        // Untouched enemies don't need a bar cluttering the path
+        let new_visibility = if ratio >= 1.0 {
+            Visibility::Hidden
+        } else {
+            Visibility::Inherited
+        };
+        if *visibility != new_visibility {
+            *visibility = new_visibility;
+        }
+
+        for &child in children.iter() {
+            if let Ok((mut fill_transform, mut sprite)) = fills.get_mut(child) {
+                fill_transform.scale.x = ratio;
+                sprite.color = Color::srgb(1.0 - ratio, 0.2 + 0.7 * ratio, 0.2);
+            }
+        }
+    }
+}
+
+const BOSS_BAR_WIDTH: f32 = 400.0;
+
+#[derive(Component)]
+#[require(LevelEntity)]
+struct BossHealthBar;
+
+#[derive(Component)]
+struct BossHealthBarFill;
+
+#[derive(Component)]
+struct BossHealthBarText;
+
+// Pins the first living boss's health to the top of the screen
+fn update_boss_health_bar(
+    mut commands: Commands,
+    bosses: Query<(&Enemy, &Boss)>,
+    bars: Query<Entity, With<BossHealthBar>>,
+    mut fills: Query<&mut Node, With<BossHealthBarFill>>,
+    mut texts: Query<&mut Text, With<BossHealthBarText>>,
+) {
+    let Some((enemy, boss)) = bosses.iter().find(|(enemy, _)| enemy.health > 0.0) else {
+        for entity in bars.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    };
+
+    let ratio = if enemy.max_health > 0.0 {
+        (enemy.health / enemy.max_health).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let label = format!("{} - Phase {}", enemy.enemy_type.name(), boss.phase_number());
+
+    if bars.is_empty() {
+        commands
+            .spawn((
+                Node {
+                    position_type: PositionType::Absolute,
+                    top: Val::Px(10.0),
+                    width: Val::Percent(100.0),
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(4.0),
+                    ..default()
+                },
+                BossHealthBar,
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    Text::new(label),
+                    TextFont {
+                        font_size: 18.0,
+                        ..default()
+                    },
+                    TextColor::WHITE,
+                    BossHealthBarText,
+                ));
+                parent
+                    .spawn((
+                        Node {
+                            width: Val::Px(BOSS_BAR_WIDTH),
+                            height: Val::Px(14.0),
+                            ..default()
+                        },
+                        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            Node {
+                                width: Val::Percent(ratio * 100.0),
+                                height: Val::Percent(100.0),
+                                ..default()
+                            },
+                            BackgroundColor(Color::srgb(0.8, 0.1, 0.1)),
+                            BossHealthBarFill,
+                        ));
+                    });
+            });
+        return;
+    }
+
+    for mut node in fills.iter_mut() {
+        node.width = Val::Percent(ratio * 100.0);
+    }
+    for mut text in texts.iter_mut() {
+        if text.0 != label {
+            text.0 = label.clone();
+        }
+    }
+}
+
 #[derive(Resource, Default)]
 pub struct MyWorldCoords(Vec2);
 
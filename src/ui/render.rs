@@ -1,5 +1,9 @@
 use crate::core::config::{WINDOW_HEIGHT, WINDOW_WIDTH};
+use crate::core::game_state::{GameState, PlayerResource};
 use crate::core::map::Map;
+use crate::entities::animation::sheet_sprite;
+use crate::entities::tower::{check_placement, BuildSelection, TowerCatalog};
+use crate::levels::level::LevelEntity;
 use crate::ui::overlay::MainCamera;
 
 use bevy::prelude::*;
@@ -10,10 +14,16 @@ pub struct RenderPlugin;
 
 impl Plugin for RenderPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Startup, setup_camera).add_systems(
-            Update,
-            (highlight_tile_under_cursor, resize_window, update_sprites),
-        );
+        app.add_systems(Startup, setup_camera)
+            .add_systems(
+                Update,
+                (highlight_tile_under_cursor, resize_window, update_sprites),
+            )
+            .add_systems(
+                Update,
+                update_placement_preview.run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(OnExit(GameState::Playing), clear_placement_preview);
     }
 }
 
@@ -77,7 +87,7 @@ pub fn highlight_tile_under_cursor(
             }
 
             let pos = UVec2::new(grid_x, grid_y);
-            let is_buildable = map.is_buildable(pos);
+            let is_buildable = map.can_build(pos);
             let what_color = if is_buildable {
                 Color::srgba(0.0, 1.0, 0.0, 0.3)
             } else {
@@ -97,4 +107,134 @@ pub fn highlight_tile_under_cursor(
     }
 }
 
+#[derive(Component)]
+#[require(LevelEntity)]
+pub struct PlacementGhost;
+
+#[derive(Component)]
+#[require(LevelEntity)]
+pub struct PlacementTooltip;
+
+// Puts the picked tower back so its preview doesn't stay on top of other screens
+fn clear_placement_preview(
+    mut commands: Commands,
+    mut build_selection: ResMut<BuildSelection>,
+    ghost: Query<Entity, With<PlacementGhost>>,
+    tooltip: Query<Entity, With<PlacementTooltip>>,
+) {
+    build_selection.0 = None;
+    for entity in ghost.iter() {
+        commands.entity(entity).despawn();
+    }
+    for entity in tooltip.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+/// Shows the tower picked in the shop under the cursor, with its range and why it can't be placed
+#[allow(clippy::too_many_arguments)]
+pub fn update_placement_preview(
+    mut commands: Commands,
+    map: Res<Map>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    build_selection: Res<BuildSelection>,
+    catalog: Res<TowerCatalog>,
+    player_resources: Res<PlayerResource>,
+    asset_server: Res<AssetServer>,
+    mut ghost: Query<(Entity, &mut Transform, &mut Sprite), With<PlacementGhost>>,
+    mut tooltip: Query<(Entity, &mut Node, &mut Text, &mut Visibility), With<PlacementTooltip>>,
+    mut gizmos: Gizmos,
+) {
+    let (camera, camera_transform) = camera_q.single();
+    let window = windows.single();
+
+    let cursor = window.cursor_position().and_then(|cursor_position| {
+        camera
+            .viewport_to_world_2d(camera_transform, cursor_position)
+            .ok()
+            .map(|world_position| (cursor_position, world_position))
+    });
+    let definition = build_selection.0.and_then(|tower_type| catalog.get(tower_type));
+
+    let (Some((cursor_position, world_position)), Some(definition)) = (cursor, definition) else {
+        for (entity, ..) in ghost.iter() {
+            commands.entity(entity).despawn();
+        }
+        for (entity, ..) in tooltip.iter() {
+            commands.entity(entity).despawn_recursive();
+        }
+        return;
+    };
+
+    let grid_pos = map.world_to_grid(world_position);
+    let tile_center = map.grid_to_world(grid_pos);
+    let placement = check_placement(&map, grid_pos, definition.cost, player_resources.money);
+
+    let (ghost_color, range_color) = if placement.is_ok() {
+        (Color::srgba(1.0, 1.0, 1.0, 0.6), Color::srgba(1.0, 1.0, 1.0, 0.5))
+    } else {
+        (Color::srgba(1.0, 0.3, 0.3, 0.6), Color::srgba(1.0, 0.3, 0.3, 0.5))
+    };
+
+    if let Ok((_, mut transform, mut sprite)) = ghost.get_single_mut() {
+        transform.translation = Vec3::new(tile_center.x, tile_center.y, 11.0);
+        sprite.color = ghost_color;
+        let image = asset_server.load(&definition.sprite);
+        if sprite.image != image {
+            *sprite = Sprite {
+                color: ghost_color,
+                ..sheet_sprite(image, definition.atlas.as_ref(), definition.atlas_layout.clone())
+            };
+        }
+    } else {
+        commands.spawn((
+            Sprite {
+                color: ghost_color,
+                ..sheet_sprite(
+                    asset_server.load(&definition.sprite),
+                    definition.atlas.as_ref(),
+                    definition.atlas_layout.clone(),
+                )
+            },
+            Transform::from_translation(Vec3::new(tile_center.x, tile_center.y, 11.0)),
+            PlacementGhost,
+        ));
+    }
+
+    gizmos.circle_2d(tile_center, definition.stats.range, range_color);
+
+    let reason = placement.err().map(|error| error.reason());
+
+    if let Ok((_, mut node, mut text, mut visibility)) = tooltip.get_single_mut() {
+        node.left = Val::Px(cursor_position.x + 16.0);
+        node.top = Val::Px(cursor_position.y + 16.0);
+        *visibility = if reason.is_some() { Visibility::Visible } else { Visibility::Hidden };
+        if let Some(reason) = reason
+            && text.0 != reason
+        {
+            text.0 = reason.to_string();
+        }
+    } else {
+        commands.spawn((
+            Text::new(reason.unwrap_or_default()),
+            TextFont {
+                font_size: 16.0,
+                ..default()
+            },
+            TextColor(Color::srgb(1.0, 0.4, 0.4)),
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(cursor_position.x + 16.0),
+                top: Val::Px(cursor_position.y + 16.0),
+                padding: UiRect::all(Val::Px(4.0)),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.7)),
+            if reason.is_some() { Visibility::Visible } else { Visibility::Hidden },
+            PlacementTooltip,
+        ));
+    }
+}
+
 pub fn render_background() {}
@@ -0,0 +1,224 @@
+use bevy::prelude::*;
+
+use crate::core::game_state::{GameState, PlayerResource};
+use crate::entities::tower::{BuildSelection, TowerCatalog, TowerType};
+use crate::ui::ui_components::{BlocksWorldClicks, BUTTON_COLOR, BUTTON_HOVER_COLOR};
+
+pub struct ShopPlugin;
+
+impl Plugin for ShopPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), setup_shop)
+            .add_systems(OnExit(GameState::Playing), cleanup_shop)
+            .add_systems(
+                Update,
+                (handle_shop_buttons, handle_shop_hotkeys, update_shop_buttons)
+                    .chain()
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+#[derive(Component)]
+struct ShopBar;
+
+#[derive(Component)]
+struct ShopButton(TowerType);
+
+#[derive(Component)]
+struct ShopCostText(TowerType);
+
+const SHOP_HOTKEYS: [KeyCode; 9] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
+const BUTTON_SELECTED_COLOR: Color = Color::srgb(0.3, 0.5, 0.3);
+const AFFORDABLE_COLOR: Color = Color::srgb(1.0, 0.85, 0.2);
+const UNAFFORDABLE_COLOR: Color = Color::srgb(0.8, 0.2, 0.2);
+
+fn setup_shop(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    catalog: Option<Res<TowerCatalog>>,
+) {
+    let Some(catalog) = catalog else { return; };
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(0.0),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            ShopBar,
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        height: Val::Px(70.0),
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(10.0),
+                        padding: UiRect::all(Val::Px(5.0)),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+                    BlocksWorldClicks,
+                ))
+                .with_children(|parent| spawn_shop_buttons(parent, &asset_server, &catalog));
+        });
+}
+
+fn spawn_shop_buttons(parent: &mut ChildBuilder, asset_server: &AssetServer, catalog: &TowerCatalog) {
+    for (index, definition) in catalog.shop_towers().enumerate() {
+        parent
+            .spawn((
+                Button,
+                Node {
+                    width: Val::Px(130.0),
+                    height: Val::Px(60.0),
+                    align_items: AlignItems::Center,
+                    column_gap: Val::Px(6.0),
+                    padding: UiRect::horizontal(Val::Px(6.0)),
+                    ..default()
+                },
+                BackgroundColor(BUTTON_COLOR),
+                ShopButton(definition.tower_type),
+            ))
+            .with_children(|parent| {
+                parent.spawn((
+                    ImageNode {
+                        image: asset_server.load(&definition.sprite),
+                        // Show the sheet's still frame rather than the whole sheet
+                        texture_atlas: definition
+                            .atlas
+                            .as_ref()
+                            .zip(definition.atlas_layout.clone())
+                            .map(|(atlas, layout)| TextureAtlas { layout, index: atlas.index }),
+                        ..default()
+                    },
+                    Node {
+                        width: Val::Px(40.0),
+                        height: Val::Px(40.0),
+                        ..default()
+                    },
+                ));
+
+                parent
+                    .spawn(Node {
+                        flex_direction: FlexDirection::Column,
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        let hotkey = if index < SHOP_HOTKEYS.len() {
+                            format!("[{}] ", index + 1)
+                        } else {
+                            String::new()
+                        };
+                        parent.spawn((
+                            Text::new(format!("{}{:?}", hotkey, definition.tower_type)),
+                            TextFont {
+                                font_size: 14.0,
+                                ..default()
+                            },
+                            TextColor::WHITE,
+                        ));
+                        parent.spawn((
+                            Text::new(format!("{}", definition.cost)),
+                            TextFont {
+                                font_size: 14.0,
+                                ..default()
+                            },
+                            TextColor(AFFORDABLE_COLOR),
+                            ShopCostText(definition.tower_type),
+                        ));
+                    });
+            });
+    }
+}
+
+fn toggle_build_selection(build_selection: &mut BuildSelection, tower_type: TowerType) {
+    build_selection.0 = if build_selection.0 == Some(tower_type) {
+        None
+    } else {
+        Some(tower_type)
+    };
+}
+
+fn handle_shop_buttons(
+    buttons: Query<(&Interaction, &ShopButton), Changed<Interaction>>,
+    mut build_selection: ResMut<BuildSelection>,
+) {
+    for (interaction, button) in buttons.iter() {
+        if *interaction == Interaction::Pressed {
+            toggle_build_selection(&mut build_selection, button.0);
+        }
+    }
+}
+
+fn handle_shop_hotkeys(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    catalog: Res<TowerCatalog>,
+    mut build_selection: ResMut<BuildSelection>,
+) {
+    for (key, definition) in SHOP_HOTKEYS.iter().zip(catalog.shop_towers()) {
+        if keyboard_input.just_pressed(*key) {
+            toggle_build_selection(&mut build_selection, definition.tower_type);
+        }
+    }
+
+    // Right click puts the tower back
+    if mouse_input.just_pressed(MouseButton::Right) && build_selection.0.is_some() {
+        build_selection.0 = None;
+    }
+}
+
+fn update_shop_buttons(
+    build_selection: Res<BuildSelection>,
+    player_resources: Res<PlayerResource>,
+    catalog: Res<TowerCatalog>,
+    mut buttons: Query<(&Interaction, &ShopButton, &mut BackgroundColor)>,
+    mut cost_texts: Query<(&ShopCostText, &mut TextColor)>,
+) {
+    for (interaction, button, mut color) in buttons.iter_mut() {
+        let new_color = if build_selection.0 == Some(button.0) {
+            BUTTON_SELECTED_COLOR
+        } else if *interaction == Interaction::Hovered {
+            BUTTON_HOVER_COLOR
+        } else {
+            BUTTON_COLOR
+        };
+        if color.0 != new_color {
+            color.0 = new_color;
+        }
+    }
+
+    for (cost_text, mut text_color) in cost_texts.iter_mut() {
+        let Some(definition) = catalog.get(cost_text.0) else { continue; };
+        let new_color = if player_resources.money >= definition.cost {
+            AFFORDABLE_COLOR
+        } else {
+            UNAFFORDABLE_COLOR
+        };
+        if text_color.0 != new_color {
+            text_color.0 = new_color;
+        }
+    }
+}
+
+fn cleanup_shop(mut commands: Commands, shop: Query<Entity, With<ShopBar>>) {
+    for entity in shop.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
@@ -0,0 +1,159 @@
+use bevy::prelude::*;
+
+use crate::core::game_state::GameState;
+use crate::entities::tower::{SelectedTower, Tower, TowerAction, TowerCatalog};
+use crate::ui::ui_components::{BlocksWorldClicks, BUTTON_COLOR, BUTTON_HOVER_COLOR};
+
+pub struct TowerPanelPlugin;
+
+impl Plugin for TowerPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (update_tower_panel, tower_panel_button_colors).run_if(in_state(GameState::Playing)),
+        )
+        .add_systems(OnExit(GameState::Playing), close_tower_panel);
+    }
+}
+
+#[derive(Component)]
+struct TowerPanel;
+
+// Drops the selection so the panel doesn't outlive the game screen
+fn close_tower_panel(
+    mut commands: Commands,
+    mut selected: ResMut<SelectedTower>,
+    panels: Query<Entity, With<TowerPanel>>,
+) {
+    selected.0 = None;
+    for entity in panels.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
+
+// Rebuilds the panel whenever the selection changes or the selected tower is upgraded
+fn update_tower_panel(
+    mut commands: Commands,
+    selected: Res<SelectedTower>,
+    towers: Query<&Tower>,
+    catalog: Res<TowerCatalog>,
+    panels: Query<Entity, With<TowerPanel>>,
+) {
+    if !selected.is_changed() {
+        return;
+    }
+
+    for entity in panels.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+
+    let Some(tower) = selected.0.and_then(|entity| towers.get(entity).ok()) else {
+        return;
+    };
+    let Some(definition) = catalog.get(tower.tower_type) else {
+        return;
+    };
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(10.0),
+                top: Val::Px(60.0),
+                width: Val::Px(220.0),
+                padding: UiRect::all(Val::Px(10.0)),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.9)),
+            BlocksWorldClicks,
+            TowerPanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(format!("{:?} (Lv {})", tower.tower_type, tower.level)),
+                TextFont {
+                    font_size: 20.0,
+                    ..default()
+                },
+                TextColor::WHITE,
+            ));
+
+            parent.spawn((
+                Text::new(format!(
+                    "Range {:.0}  Damage {:.0}  Rate {:.1}",
+                    tower.range, tower.damage, tower.fire_rate
+                )),
+                TextFont {
+                    font_size: 14.0,
+                    ..default()
+                },
+                TextColor::WHITE,
+            ));
+
+            if let Some(cost) = definition.upgrade_cost(tower.level) {
+                spawn_panel_button(parent, &format!("Upgrade ({})", cost), TowerAction::Upgrade);
+            }
+
+            if let Some(cost) = definition.branch_cost() {
+                for &option in definition.branch_options(tower.level) {
+                    spawn_panel_button(
+                        parent,
+                        &format!("{:?} ({})", option, cost),
+                        TowerAction::Branch(option),
+                    );
+                }
+            }
+
+            spawn_panel_button(
+                parent,
+                &format!("Target: {:?}", tower.priority),
+                TowerAction::CyclePriority,
+            );
+            spawn_panel_button(
+                parent,
+                &format!("Sell (+{})", tower.sell_value(&catalog)),
+                TowerAction::Sell,
+            );
+        });
+}
+
+fn spawn_panel_button(parent: &mut ChildBuilder, text: &str, action: TowerAction) {
+    parent
+        .spawn((
+            Button,
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Px(32.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(BUTTON_COLOR),
+            action,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(text),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor::WHITE,
+            ));
+        });
+}
+
+type ChangedPanelButton = (Changed<Interaction>, With<TowerAction>);
+
+fn tower_panel_button_colors(
+    mut buttons: Query<(&Interaction, &mut BackgroundColor), ChangedPanelButton>,
+) {
+    for (interaction, mut color) in buttons.iter_mut() {
+        *color = match *interaction {
+            Interaction::Pressed | Interaction::Hovered => BackgroundColor(BUTTON_HOVER_COLOR),
+            Interaction::None => BackgroundColor(BUTTON_COLOR),
+        };
+    }
+}
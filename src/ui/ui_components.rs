@@ -1,5 +1,14 @@
 use bevy::prelude::*;
 
+// Colours shared by the in-game panel buttons
+pub const BUTTON_COLOR: Color = Color::srgb(0.15, 0.15, 0.15);
+pub const BUTTON_HOVER_COLOR: Color = Color::srgb(0.25, 0.25, 0.25);
+
+/// Lets world click handlers see the cursor is over this node, see `is_pointer_over_ui`
+#[derive(Component, Default)]
+#[require(Interaction)]
+pub struct BlocksWorldClicks;
+
 pub struct UiPlugin;
 
 impl Plugin for UiPlugin {
@@ -0,0 +1,150 @@
+use bevy::prelude::*;
+
+use crate::core::game_state::GameState;
+use crate::levels::level::{Level, SendNextWaveEvent};
+use crate::ui::ui_components::{BlocksWorldClicks, BUTTON_COLOR, BUTTON_HOVER_COLOR};
+
+pub struct WavePanelPlugin;
+
+impl Plugin for WavePanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::Playing), setup_wave_panel)
+            .add_systems(OnExit(GameState::Playing), cleanup_wave_panel)
+            .add_systems(
+                Update,
+                (handle_send_wave_input, update_wave_panel)
+                    .chain()
+                    .run_if(in_state(GameState::Playing).and(resource_exists::<Level>)),
+            );
+    }
+}
+
+#[derive(Component)]
+struct WavePanel;
+
+#[derive(Component)]
+struct WaveStatusText;
+
+#[derive(Component)]
+struct SendWaveButton;
+
+#[derive(Component)]
+struct SendWaveText;
+
+const SEND_WAVE_HOTKEY: KeyCode = KeyCode::KeyN;
+
+fn setup_wave_panel(mut commands: Commands) {
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(10.0),
+                top: Val::Px(60.0),
+                padding: UiRect::all(Val::Px(8.0)),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.0),
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.8)),
+            BlocksWorldClicks,
+            WavePanel,
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::new(""),
+                TextFont {
+                    font_size: 16.0,
+                    ..default()
+                },
+                TextColor::WHITE,
+                WaveStatusText,
+            ));
+            parent
+                .spawn((
+                    Button,
+                    Node {
+                        padding: UiRect::axes(Val::Px(8.0), Val::Px(4.0)),
+                        justify_content: JustifyContent::Center,
+                        ..default()
+                    },
+                    BackgroundColor(BUTTON_COLOR),
+                    SendWaveButton,
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        Text::new(""),
+                        TextFont {
+                            font_size: 14.0,
+                            ..default()
+                        },
+                        TextColor::WHITE,
+                        SendWaveText,
+                    ));
+                });
+        });
+}
+
+fn handle_send_wave_input(
+    keyboard_input: Res<ButtonInput<KeyCode>>,
+    buttons: Query<&Interaction, (Changed<Interaction>, With<SendWaveButton>)>,
+    mut send_events: EventWriter<SendNextWaveEvent>,
+) {
+    let clicked = buttons.iter().any(|interaction| *interaction == Interaction::Pressed);
+    if clicked || keyboard_input.just_pressed(SEND_WAVE_HOTKEY) {
+        send_events.send(SendNextWaveEvent);
+    }
+}
+
+fn update_wave_panel(
+    level: Res<Level>,
+    mut status_texts: Query<&mut Text, (With<WaveStatusText>, Without<SendWaveText>)>,
+    mut send_texts: Query<&mut Text, (With<SendWaveText>, Without<WaveStatusText>)>,
+    mut buttons: Query<(&Interaction, &mut BackgroundColor, &mut Visibility), With<SendWaveButton>>,
+) {
+    let status = if level.completed {
+        "All waves cleared".to_string()
+    } else if let Some(timer) = level.next_wave_timer.as_ref().filter(|_| level.has_next_wave()) {
+        format!(
+            "Wave {}/{} in {:.0}s",
+            level.current_wave_index + 1,
+            level.waves.len(),
+            timer.remaining_secs().ceil()
+        )
+    } else {
+        format!("Wave {}/{}", level.current_wave_index, level.waves.len())
+    };
+    for mut text in status_texts.iter_mut() {
+        if text.0 != status {
+            text.0 = status.clone();
+        }
+    }
+
+    let label = format!("Send next wave (+{}) [N]", level.early_call_bonus());
+    for mut text in send_texts.iter_mut() {
+        if text.0 != label {
+            text.0 = label.clone();
+        }
+    }
+
+    let can_send = level.has_next_wave() && !level.completed;
+    for (interaction, mut color, mut visibility) in buttons.iter_mut() {
+        let new_visibility = if can_send { Visibility::Inherited } else { Visibility::Hidden };
+        if *visibility != new_visibility {
+            *visibility = new_visibility;
+        }
+        let new_color = if *interaction == Interaction::None {
+            BUTTON_COLOR
+        } else {
+            BUTTON_HOVER_COLOR
+        };
+        if color.0 != new_color {
+            color.0 = new_color;
+        }
+    }
+}
+
+fn cleanup_wave_panel(mut commands: Commands, panels: Query<Entity, With<WavePanel>>) {
+    for entity in panels.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}